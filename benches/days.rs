@@ -0,0 +1,85 @@
+//! Per-day, per-part timings over the committed inputs.
+//!
+//! Run with `cargo bench`, optionally followed by day numbers to restrict the set, e.g.
+//! `cargo bench -- 6 8`.
+
+use std::{fs, time::Duration};
+
+use aoc22::{days, days::Timings};
+
+const ITERATIONS: u32 = 20;
+
+#[derive(Default)]
+struct Stats {
+    /// `None` until the first sample, since a coarse clock can measure a real zero.
+    min: Option<Timings>,
+    sum: Timings,
+}
+
+impl Stats {
+    fn add(&mut self, t: Timings) {
+        self.min = Some(match self.min {
+            None => t,
+            Some(min) => Timings {
+                parse: min.parse.min(t.parse),
+                part1: min.part1.min(t.part1),
+                part2: min.part2.min(t.part2),
+            },
+        });
+        self.sum.parse += t.parse;
+        self.sum.part1 += t.part1;
+        self.sum.part2 += t.part2;
+    }
+
+    fn mean(&self, n: u32) -> Timings {
+        Timings {
+            parse: self.sum.parse / n,
+            part1: self.sum.part1 / n,
+            part2: self.sum.part2 / n,
+        }
+    }
+}
+
+fn micros(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1_000_000.0
+}
+
+fn main() {
+    let selected = std::env::args()
+        .skip(1)
+        .filter_map(|arg| arg.parse::<u8>().ok())
+        .collect::<Vec<u8>>();
+
+    println!(
+        "{:>3} {:>6} {:>14} {:>14} {:>14}",
+        "Day", "", "Parse (µs)", "Part 1 (µs)", "Part 2 (µs)"
+    );
+    for solution in days::REGISTRY.iter() {
+        let day = solution.day();
+        if !selected.is_empty() && !selected.contains(&day) {
+            continue;
+        }
+        // Only days with a committed input are benchmarked.
+        let Ok(input) = fs::read(days::input_path(day)) else {
+            continue;
+        };
+        let mut stats = Stats::default();
+        for _ in 0..ITERATIONS {
+            let answers = solution
+                .solve(&mut input.as_slice(), None)
+                .expect("committed input must solve");
+            stats.add(answers.timings);
+        }
+        let min = stats.min.expect("ITERATIONS is positive");
+        for (label, t) in [("min", min), ("mean", stats.mean(ITERATIONS))] {
+            println!(
+                "{:>3} {:>6} {:>14.1} {:>14.1} {:>14.1}",
+                day,
+                label,
+                micros(t.parse),
+                micros(t.part1),
+                micros(t.part2)
+            );
+        }
+    }
+}
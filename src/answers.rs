@@ -0,0 +1,216 @@
+//! Answers confirmed by the puzzle site, kept in `answers.toml` so later runs can flag
+//! regressions.
+//!
+//! The file has one table per day with a string per confirmed part:
+//!
+//! ```toml
+//! [day5]
+//! part2 = "JSDHQMZGF"
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+    fs,
+    io::{self, ErrorKind},
+    path::Path,
+};
+
+use crate::error::AocError;
+
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AnswerStore {
+    /// Answer by (day, part).
+    answers: BTreeMap<(u8, u8), String>,
+}
+
+impl AnswerStore {
+    /// Reads the store at `path`; a missing file is an empty store.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<AnswerStore, AocError> {
+        let path = path.as_ref();
+        match fs::read_to_string(path) {
+            Ok(text) => AnswerStore::parse(&text).map_err(|reason| {
+                io::Error::new(
+                    ErrorKind::InvalidData,
+                    format!("{}: {}", path.display(), reason),
+                )
+                .into()
+            }),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(AnswerStore::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), AocError> {
+        Ok(fs::write(path, self.to_string())?)
+    }
+
+    pub fn parse(text: &str) -> Result<AnswerStore, String> {
+        let mut store = AnswerStore::default();
+        let mut day = None;
+        for (line_idx, line) in text.lines().enumerate() {
+            let line = line.trim();
+            let err = |reason: &str| format!("line {}: {}", line_idx + 1, reason);
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(table) = line.strip_prefix('[') {
+                let number = table
+                    .strip_suffix(']')
+                    .and_then(|t| t.trim().strip_prefix("day"))
+                    .and_then(|n| n.parse::<u8>().ok())
+                    .ok_or_else(|| err("expected [dayN]"))?;
+                day = Some(number);
+                continue;
+            }
+
+            let day = day.ok_or_else(|| err("answer outside of a [dayN] table"))?;
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| err("expected partN = \"answer\""))?;
+            let part = match key.trim() {
+                "part1" => 1,
+                "part2" => 2,
+                _ => return Err(err("expected part1 or part2")),
+            };
+            let value = unescape(value.trim()).ok_or_else(|| err("invalid string"))?;
+            store.answers.insert((day, part), value);
+        }
+        Ok(store)
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn record(&mut self, day: u8, part: u8, answer: &str) {
+        self.answers.insert((day, part), answer.to_owned());
+    }
+
+    /// The recorded answer, when one exists and differs from `answer`.
+    pub fn regression(&self, day: u8, part: u8, answer: &str) -> Option<&str> {
+        self.get(day, part).filter(|recorded| *recorded != answer)
+    }
+
+    /// `answer` followed by a warning if it no longer matches the recorded one.
+    pub fn annotate(&self, day: u8, part: u8, answer: &str) -> String {
+        match self.regression(day, part, answer) {
+            Some(recorded) => format!(
+                "{} (REGRESSION: {} has {})",
+                answer,
+                ANSWERS_FILE,
+                escape(recorded)
+            ),
+            None => answer.to_owned(),
+        }
+    }
+}
+
+impl Display for AnswerStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut current = None;
+        for ((day, part), answer) in &self.answers {
+            if current != Some(day) {
+                if current.is_some() {
+                    writeln!(f)?;
+                }
+                writeln!(f, "[day{}]", day)?;
+                current = Some(day);
+            }
+            writeln!(f, "part{} = {}", part, escape(answer))?;
+        }
+        Ok(())
+    }
+}
+
+fn escape(value: &str) -> String {
+    let mut out = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            _ => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn unescape(value: &str) -> Option<String> {
+    let mut chars = value.strip_prefix('"')?.strip_suffix('"')?.chars();
+    let mut out = String::new();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => out.push(match chars.next()? {
+                'n' => '\n',
+                c @ ('"' | '\\') => c,
+                _ => return None,
+            }),
+            '"' => return None,
+            _ => out.push(c),
+        }
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STORE: &str = "[day1]
+part1 = \"74394\"
+
+[day5]
+part2 = \"JSDHQMZGF\"
+";
+
+    #[test]
+    fn parse_and_print_round_trip() {
+        let store = AnswerStore::parse(STORE).unwrap();
+        assert_eq!(store.get(1, 1), Some("74394"));
+        assert_eq!(store.get(1, 2), None);
+        assert_eq!(store.get(5, 2), Some("JSDHQMZGF"));
+        assert_eq!(store.to_string(), STORE);
+    }
+
+    #[test]
+    fn record_keeps_tables_ordered() {
+        let mut store = AnswerStore::parse(STORE).unwrap();
+        store.record(3, 1, "8039");
+        store.record(10, 2, "\n##\n#\"");
+        let text = store.to_string();
+        assert!(text.contains("[day1]\npart1 = \"74394\"\n\n[day3]\npart1 = \"8039\"\n"));
+        assert_eq!(AnswerStore::parse(&text).unwrap(), store);
+    }
+
+    #[test]
+    fn regressions_are_flagged() {
+        let store = AnswerStore::parse(STORE).unwrap();
+        assert_eq!(store.regression(1, 1, "74394"), None);
+        assert_eq!(store.regression(1, 1, "74395"), Some("74394"));
+        assert_eq!(store.regression(2, 1, "1"), None);
+        assert_eq!(
+            store.annotate(1, 1, "1"),
+            "1 (REGRESSION: answers.toml has \"74394\")"
+        );
+    }
+
+    #[test]
+    fn malformed_store_reports_line() {
+        assert_eq!(
+            AnswerStore::parse("part1 = \"1\"\n"),
+            Err("line 1: answer outside of a [dayN] table".to_owned())
+        );
+        assert_eq!(
+            AnswerStore::parse("[day1]\npart3 = \"1\"\n"),
+            Err("line 2: expected part1 or part2".to_owned())
+        );
+        assert_eq!(
+            AnswerStore::parse("[day1]\npart1 = 1\n"),
+            Err("line 2: invalid string".to_owned())
+        );
+    }
+}
@@ -0,0 +1,368 @@
+use std::fmt::{self, Display};
+
+use aoc22::days::Part;
+
+pub const USAGE: &str = "\
+Usage:
+    aoc22 run --all [--time] [--format <text|json>]
+    aoc22 run --day <N> [--part <1|2>] [--input <FILE|->] [--time] [--format <text|json>]
+    aoc22 run --day 5 [--part <1|2>] [--input <FILE|->] --trace
+    aoc22 fetch --day <N>
+    aoc22 submit --day <N> --part <1|2>
+    aoc22 lint --day <N> [--input <FILE|->]
+
+Options:
+    -a, --all           run every registered day against data/input_dayN
+    -d, --day <N>       run a single day
+    -p, --part <1|2>    only run the given part
+    -i, --input <FILE>  read the puzzle input from FILE, or stdin for '-'
+    -t, --time          report parse, part 1 and part 2 durations per day
+    -f, --format <FMT>  print answers as text, or as one JSON record per line
+                        with day, part, answer and elapsed_ms
+    -T, --trace         print the crate drawing after every move (day 5 only)
+    -h, --help          print this message
+
+Fetching reads the session cookie from AOC_SESSION or aoc22.toml and
+saves the input to data/input_dayN unless that file already exists.
+Submitting solves data/input_dayN, posts the answer and records it in
+answers.toml if it is correct; runs then flag answers that changed.
+Linting reports every malformed line of a day's input; only day 3 has
+a linter so far.";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Input {
+    Default,
+    Stdin,
+    File(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Days {
+    All,
+    Single(u8),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RunArgs {
+    pub days: Days,
+    pub part: Option<Part>,
+    pub input: Input,
+    pub time: bool,
+    pub format: Format,
+    pub trace: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    Run(RunArgs),
+    Fetch(u8),
+    Submit(u8, Part),
+    Lint(u8, Input),
+    Help,
+}
+
+#[derive(Debug)]
+pub struct CliError(String);
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for CliError {}
+
+fn value<I>(flag: &str, args: &mut I) -> Result<String, CliError>
+where
+    I: Iterator<Item = String>,
+{
+    args.next()
+        .ok_or_else(|| CliError(format!("missing value for {}", flag)))
+}
+
+fn parse_day(v: &str) -> Result<u8, CliError> {
+    v.parse::<u8>()
+        .map_err(|_| CliError(format!("invalid day '{}'", v)))
+}
+
+fn parse_part(v: &str) -> Result<Part, CliError> {
+    match v {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        v => Err(CliError(format!("invalid part '{}'", v))),
+    }
+}
+
+fn parse_input(v: String) -> Input {
+    match v.as_str() {
+        "-" => Input::Stdin,
+        _ => Input::File(v),
+    }
+}
+
+fn parse_run<I>(mut args: I) -> Result<RunArgs, CliError>
+where
+    I: Iterator<Item = String>,
+{
+    let mut all = false;
+    let mut day = None;
+    let mut part = None;
+    let mut input = Input::Default;
+    let mut time = false;
+    let mut format = Format::Text;
+    let mut trace = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-a" | "--all" => all = true,
+            "-t" | "--time" => time = true,
+            "-T" | "--trace" => trace = true,
+            "-d" | "--day" => {
+                day = Some(parse_day(&value(&arg, &mut args)?)?);
+            }
+            "-p" | "--part" => part = Some(parse_part(&value(&arg, &mut args)?)?),
+            "-f" | "--format" => {
+                format = match value(&arg, &mut args)?.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    v => return Err(CliError(format!("invalid format '{}'", v))),
+                }
+            }
+            "-i" | "--input" => input = parse_input(value(&arg, &mut args)?),
+            _ => return Err(CliError(format!("unexpected argument '{}'", arg))),
+        }
+    }
+
+    let days = match (all, day) {
+        (true, None) => Days::All,
+        (false, Some(day)) => Days::Single(day),
+        (true, Some(_)) => return Err(CliError("--all conflicts with --day".to_owned())),
+        (false, None) => return Err(CliError("expected --day <N> or --all".to_owned())),
+    };
+    if days == Days::All && input != Input::Default {
+        return Err(CliError("--input requires a single --day".to_owned()));
+    }
+    if time && format == Format::Json {
+        return Err(CliError(
+            "--time conflicts with --format json, which already reports elapsed_ms".to_owned(),
+        ));
+    }
+
+    if trace && days == Days::All {
+        return Err(CliError("--trace requires a single --day".to_owned()));
+    }
+    if trace && (time || format == Format::Json) {
+        return Err(CliError(
+            "--trace conflicts with --time and --format json".to_owned(),
+        ));
+    }
+
+    Ok(RunArgs {
+        days,
+        part,
+        input,
+        time,
+        format,
+        trace,
+    })
+}
+
+fn parse_fetch<I>(mut args: I) -> Result<u8, CliError>
+where
+    I: Iterator<Item = String>,
+{
+    let mut day = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-d" | "--day" => day = Some(parse_day(&value(&arg, &mut args)?)?),
+            _ => return Err(CliError(format!("unexpected argument '{}'", arg))),
+        }
+    }
+    day.ok_or_else(|| CliError("expected --day <N>".to_owned()))
+}
+
+fn parse_submit<I>(mut args: I) -> Result<(u8, Part), CliError>
+where
+    I: Iterator<Item = String>,
+{
+    let mut day = None;
+    let mut part = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-d" | "--day" => day = Some(parse_day(&value(&arg, &mut args)?)?),
+            "-p" | "--part" => part = Some(parse_part(&value(&arg, &mut args)?)?),
+            _ => return Err(CliError(format!("unexpected argument '{}'", arg))),
+        }
+    }
+    match (day, part) {
+        (Some(day), Some(part)) => Ok((day, part)),
+        _ => Err(CliError("expected --day <N> and --part <1|2>".to_owned())),
+    }
+}
+
+fn parse_lint<I>(mut args: I) -> Result<(u8, Input), CliError>
+where
+    I: Iterator<Item = String>,
+{
+    let mut day = None;
+    let mut input = Input::Default;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-d" | "--day" => day = Some(parse_day(&value(&arg, &mut args)?)?),
+            "-i" | "--input" => input = parse_input(value(&arg, &mut args)?),
+            _ => return Err(CliError(format!("unexpected argument '{}'", arg))),
+        }
+    }
+    match day {
+        Some(day) => Ok((day, input)),
+        None => Err(CliError("expected --day <N>".to_owned())),
+    }
+}
+
+/// Parses the arguments following the program name. No arguments runs every day.
+pub fn parse_args<I>(args: I) -> Result<Command, CliError>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+    match args.next().as_deref() {
+        None => Ok(Command::Run(RunArgs {
+            days: Days::All,
+            part: None,
+            input: Input::Default,
+            time: false,
+            format: Format::Text,
+            trace: false,
+        })),
+        Some("run") => parse_run(args).map(Command::Run),
+        Some("fetch") => parse_fetch(args).map(Command::Fetch),
+        Some("submit") => parse_submit(args).map(|(day, part)| Command::Submit(day, part)),
+        Some("lint") => parse_lint(args).map(|(day, input)| Command::Lint(day, input)),
+        Some("-h" | "--help" | "help") => Ok(Command::Help),
+        Some(cmd) => Err(CliError(format!("unknown command '{}'", cmd))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<Command, CliError> {
+        parse_args(args.split_whitespace().map(str::to_owned))
+    }
+
+    #[test]
+    fn run_single_day() {
+        assert_eq!(
+            parse("run --day 7 --part 2 --input path/to/file").unwrap(),
+            Command::Run(RunArgs {
+                days: Days::Single(7),
+                part: Some(Part::Two),
+                input: Input::File("path/to/file".to_owned()),
+                time: false,
+                format: Format::Text,
+                trace: false,
+            })
+        );
+        assert_eq!(
+            parse("run -d 3 -i - --time").unwrap(),
+            Command::Run(RunArgs {
+                days: Days::Single(3),
+                part: None,
+                input: Input::Stdin,
+                time: true,
+                format: Format::Text,
+                trace: false,
+            })
+        );
+        assert_eq!(
+            parse("run --all --format json").unwrap(),
+            Command::Run(RunArgs {
+                days: Days::All,
+                part: None,
+                input: Input::Default,
+                time: false,
+                format: Format::Json,
+                trace: false,
+            })
+        );
+    }
+
+    #[test]
+    fn run_with_trace() {
+        assert_eq!(
+            parse("run -d 5 -p 1 --trace").unwrap(),
+            Command::Run(RunArgs {
+                days: Days::Single(5),
+                part: Some(Part::One),
+                input: Input::Default,
+                time: false,
+                format: Format::Text,
+                trace: true,
+            })
+        );
+    }
+
+    #[test]
+    fn no_arguments_runs_all() {
+        assert_eq!(parse("").unwrap(), parse("run --all").unwrap());
+    }
+
+    #[test]
+    fn fetch_day() {
+        assert_eq!(parse("fetch --day 12").unwrap(), Command::Fetch(12));
+        assert_eq!(parse("fetch -d 1").unwrap(), Command::Fetch(1));
+    }
+
+    #[test]
+    fn submit_part() {
+        assert_eq!(
+            parse("submit --day 5 --part 2").unwrap(),
+            Command::Submit(5, Part::Two)
+        );
+        assert_eq!(
+            parse("submit -p 1 -d 3").unwrap(),
+            Command::Submit(3, Part::One)
+        );
+    }
+
+    #[test]
+    fn lint_day() {
+        assert_eq!(
+            parse("lint --day 3").unwrap(),
+            Command::Lint(3, Input::Default)
+        );
+        assert_eq!(
+            parse("lint -i gen.txt -d 3").unwrap(),
+            Command::Lint(3, Input::File("gen.txt".to_owned()))
+        );
+    }
+
+    #[test]
+    fn invalid_arguments() {
+        assert!(parse("run").is_err());
+        assert!(parse("run --day").is_err());
+        assert!(parse("run --day x").is_err());
+        assert!(parse("run --day 1 --part 3").is_err());
+        assert!(parse("run --all --day 1").is_err());
+        assert!(parse("run --all --input foo").is_err());
+        assert!(parse("run --all --format yaml").is_err());
+        assert!(parse("run --all --format json --time").is_err());
+        assert!(parse("run --all --trace").is_err());
+        assert!(parse("run --day 5 --trace --format json").is_err());
+        assert!(parse("run --day 5 --trace --time").is_err());
+        assert!(parse("walk").is_err());
+        assert!(parse("fetch").is_err());
+        assert!(parse("fetch --day 1 --part 1").is_err());
+        assert!(parse("submit --day 1").is_err());
+        assert!(parse("submit --part 1").is_err());
+        assert!(parse("lint").is_err());
+        assert!(parse("lint --day 3 --part 1").is_err());
+    }
+}
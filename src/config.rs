@@ -0,0 +1,120 @@
+//! Settings for talking to the puzzle site.
+//!
+//! Values come from the environment first and then from `aoc22.toml` (or the file named by
+//! `AOC22_CONFIG`), which holds `key = "value"` lines:
+//!
+//! ```toml
+//! session = "53616c7465645f5f..."
+//! base_url = "http://127.0.0.1:8000/2022"
+//! ```
+
+use std::{collections::HashMap, env, error::Error, fs, io};
+
+pub const CONFIG_FILE: &str = "aoc22.toml";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com/2022";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Config {
+    /// Year URL, under which `/day/N/input` lives.
+    pub base_url: String,
+    pub session: Option<String>,
+}
+
+impl Config {
+    /// Reads the config file, if any, and applies `AOC_SESSION` and `AOC_BASE_URL` on top.
+    pub fn load() -> Result<Config, Box<dyn Error>> {
+        let path = env::var("AOC22_CONFIG").unwrap_or_else(|_| CONFIG_FILE.to_owned());
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => Some(text),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(format!("cannot read {}: {}", path, e).into()),
+        };
+        Config::from_sources(text.as_deref(), |key| env::var(key).ok())
+            .map_err(|e| format!("{}: {}", path, e).into())
+    }
+
+    pub fn from_sources<F>(file: Option<&str>, env: F) -> Result<Config, String>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let mut values = match file {
+            Some(text) => parse_file(text)?,
+            None => HashMap::new(),
+        };
+        Ok(Config {
+            base_url: env("AOC_BASE_URL")
+                .or_else(|| values.remove("base_url"))
+                .unwrap_or_else(|| DEFAULT_BASE_URL.to_owned())
+                .trim_end_matches('/')
+                .to_owned(),
+            session: env("AOC_SESSION").or_else(|| values.remove("session")),
+        })
+    }
+
+    pub fn session(&self) -> Result<&str, Box<dyn Error>> {
+        self.session.as_deref().ok_or_else(|| {
+            format!(
+                "no session cookie: set AOC_SESSION or 'session' in {}",
+                CONFIG_FILE
+            )
+            .into()
+        })
+    }
+}
+
+fn parse_file(text: &str) -> Result<HashMap<String, String>, String> {
+    let mut values = HashMap::new();
+    for (line_idx, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let value = line.split_once('=').and_then(|(key, value)| {
+            let value = value.trim().strip_prefix('"')?.strip_suffix('"')?;
+            Some((key.trim().to_owned(), value.to_owned()))
+        });
+        match value {
+            Some((key, value)) => {
+                values.insert(key, value);
+            }
+            None => return Err(format!("line {}: expected key = \"value\"", line_idx + 1)),
+        }
+    }
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_env(_: &str) -> Option<String> {
+        None
+    }
+
+    #[test]
+    fn defaults_without_sources() {
+        let config = Config::from_sources(None, no_env).unwrap();
+        assert_eq!(config.base_url, DEFAULT_BASE_URL);
+        assert!(config.session().is_err());
+    }
+
+    #[test]
+    fn file_values_and_env_override() {
+        let file = "# local mock\nsession = \"abc\"\nbase_url = \"http://127.0.0.1:8000/2022/\"\n";
+        let config = Config::from_sources(Some(file), no_env).unwrap();
+        assert_eq!(config.base_url, "http://127.0.0.1:8000/2022");
+        assert_eq!(config.session().unwrap(), "abc");
+
+        let env = |key: &str| (key == "AOC_SESSION").then(|| "from-env".to_owned());
+        let config = Config::from_sources(Some(file), env).unwrap();
+        assert_eq!(config.session().unwrap(), "from-env");
+    }
+
+    #[test]
+    fn malformed_line_is_error() {
+        assert_eq!(
+            Config::from_sources(Some("session = abc\n"), no_env),
+            Err("line 1: expected key = \"value\"".to_owned())
+        );
+    }
+}
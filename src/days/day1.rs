@@ -1,36 +1,337 @@
 // mod day1
 
-use std::cmp::max;
-use std::error::Error;
-use std::fs::File;
+use std::cmp::Reverse;
 use std::io::BufRead;
-use std::io::BufReader;
 use std::str::FromStr;
 
-pub fn print_answer() {
-    println!(
-        "{}",
-        read_calories(BufReader::new(File::open("data/input_day1").unwrap())).unwrap()
-    );
+use super::Solution;
+use crate::error::AocError;
+
+pub struct Day1;
+
+/// Part 2 sums the calories carried by this many elves.
+pub const TOP_ELVES: usize = 3;
+
+/// Largest number of bins [`Expedition::histogram`] will allocate.
+pub const MAX_HISTOGRAM_BINS: usize = 10_000;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+    type Input = Expedition;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Self::Input, AocError> {
+        read_expedition(reader)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Answer1, AocError> {
+        input.top_calories(1)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Self::Answer2, AocError> {
+        input.top_calories(TOP_ELVES)
+    }
+}
+
+pub fn print_answer() -> Result<(), AocError> {
+    super::print_solution(&Day1)
+}
+
+/// The calories of every food item one elf carries.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Elf {
+    pub items: Vec<u64>,
+}
+
+impl Elf {
+    /// Total calories, or `None` if they do not fit in a `u64`.
+    pub fn total(&self) -> Option<u64> {
+        self.items
+            .iter()
+            .try_fold(0u64, |sum, c| sum.checked_add(*c))
+    }
+}
+
+/// An elf's position in the input, counting from 0, and the calories it carries.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RankedElf {
+    pub index: usize,
+    pub total: u64,
+}
+
+/// Counts of elf totals in equal-width bins; bin `i` covers `start + i * width` up to, but
+/// excluding, the next bin.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Histogram {
+    pub start: u64,
+    pub width: u64,
+    pub counts: Vec<usize>,
+}
+
+/// Every elf in input order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Expedition {
+    pub elves: Vec<Elf>,
 }
 
-fn read_calories<R>(reader: R) -> Result<i32, Box<dyn Error>>
+impl Expedition {
+    /// Calories carried by each elf, or `None` if any total overflows a `u64`.
+    pub fn totals(&self) -> Option<Vec<u64>> {
+        self.elves.iter().map(Elf::total).collect()
+    }
+
+    fn sorted_totals(&self) -> Option<Vec<u64>> {
+        let mut totals = self.totals()?;
+        totals.sort_unstable();
+        Some(totals)
+    }
+
+    pub fn item_count(&self) -> usize {
+        self.elves.iter().map(|elf| elf.items.len()).sum()
+    }
+
+    pub fn ranking(&self) -> Option<Vec<RankedElf>> {
+        self.totals().map(|totals| rank_elves(&totals))
+    }
+
+    /// Calories carried by the `n` elves carrying the most.
+    pub fn top_calories(&self, n: usize) -> Result<u64, AocError> {
+        let totals = self
+            .totals()
+            .ok_or_else(|| AocError::no_solution(Day1::DAY, "an elf's calories overflow u64"))?;
+        top_calories(&totals, n)
+    }
+
+    /// Mean calories per elf; `None` without elves or on overflow.
+    pub fn mean(&self) -> Option<f64> {
+        let totals = self.totals()?;
+        let sum = totals.iter().map(|t| *t as u128).sum::<u128>();
+        (!totals.is_empty()).then(|| sum as f64 / totals.len() as f64)
+    }
+
+    /// Middle total, or the mean of the two middle totals for an even number of elves.
+    pub fn median(&self) -> Option<f64> {
+        let totals = self.sorted_totals()?;
+        let mid = totals.len() / 2;
+        match totals.len() {
+            0 => None,
+            n if n % 2 == 1 => Some(totals[mid] as f64),
+            _ => Some((totals[mid - 1] as f64 + totals[mid] as f64) / 2.0),
+        }
+    }
+
+    /// Smallest total such that at least `p` percent of elves carry no more (nearest rank).
+    pub fn percentile(&self, p: f64) -> Option<u64> {
+        if !(0.0..=100.0).contains(&p) {
+            return None;
+        }
+        let totals = self.sorted_totals()?;
+        let rank = (p / 100.0 * totals.len() as f64).ceil() as usize;
+        totals.get(rank.saturating_sub(1)).copied()
+    }
+
+    /// Totals grouped into bins of `width` calories, starting at the bin holding the smallest;
+    /// `None` if that takes more than [`MAX_HISTOGRAM_BINS`] bins.
+    pub fn histogram(&self, width: u64) -> Option<Histogram> {
+        if width == 0 {
+            return None;
+        }
+        let totals = self.sorted_totals()?;
+        let start = totals.first()? / width * width;
+        let bins = usize::try_from((totals.last()? - start) / width)
+            .ok()
+            .filter(|last_bin| *last_bin < MAX_HISTOGRAM_BINS)?
+            + 1;
+        let mut counts = vec![0; bins];
+        for total in &totals {
+            counts[((total - start) / width) as usize] += 1;
+        }
+        Some(Histogram {
+            start,
+            width,
+            counts,
+        })
+    }
+}
+
+/// Parses blank-line separated groups of calories, one elf per group. The last group does not
+/// need a trailing blank line.
+pub fn read_expedition<R>(reader: R) -> Result<Expedition, AocError>
 where
     R: BufRead,
 {
-    Ok(reader
-        .lines()
-        .try_fold(
-            (0, 0),
-            |(acc, max_sum), line| -> Result<(i32, i32), Box<dyn Error>> {
-                match line?.trim() {
-                    "" => Ok((0, max(acc, max_sum))),
-                    l => {
-                        let new_acc = acc + i32::from_str(l)?;
-                        Ok((new_acc, max(new_acc, max_sum)))
-                    }
-                }
-            },
-        )?
-        .1)
+    let mut elves = Vec::new();
+    let mut current: Option<(Elf, u64)> = None;
+    for (line_idx, line) in reader.lines().enumerate() {
+        let line = line?;
+        match line.trim() {
+            "" => elves.extend(current.take().map(|(elf, _)| elf)),
+            l => {
+                let column = line.len() - line.trim_start().len() + 1;
+                let parse_err = |reason: String| {
+                    AocError::parse(Day1::DAY, line_idx + 1, column, &line, reason)
+                };
+                let calories = u64::from_str(l).map_err(|e| parse_err(e.to_string()))?;
+                let (elf, total) = current.get_or_insert_with(Default::default);
+                *total = total.checked_add(calories).ok_or_else(|| {
+                    parse_err("elf carries more than u64::MAX calories".to_owned())
+                })?;
+                elf.items.push(calories);
+            }
+        }
+    }
+    elves.extend(current.map(|(elf, _)| elf));
+    Ok(Expedition { elves })
+}
+
+/// Elves ordered from most to fewest calories; ties keep input order.
+pub fn rank_elves(totals: &[u64]) -> Vec<RankedElf> {
+    let mut ranking = totals
+        .iter()
+        .enumerate()
+        .map(|(index, total)| RankedElf {
+            index,
+            total: *total,
+        })
+        .collect::<Vec<RankedElf>>();
+    ranking.sort_by_key(|elf| Reverse(elf.total));
+    ranking
+}
+
+/// Calories carried by the `n` elves carrying the most.
+pub fn top_calories(totals: &[u64], n: usize) -> Result<u64, AocError> {
+    if totals.len() < n {
+        return Err(AocError::no_solution(
+            Day1::DAY,
+            format!("only {} elves, need {}", totals.len(), n),
+        ));
+    }
+    rank_elves(totals)
+        .iter()
+        .take(n)
+        .try_fold(0u64, |sum, elf| sum.checked_add(elf.total))
+        .ok_or_else(|| AocError::no_solution(Day1::DAY, "sum of calories overflows u64"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000
+";
+
+    #[test]
+    fn example_max_calories() {
+        let expedition = read_expedition(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(expedition.elves[0].items, [1000, 2000, 3000]);
+        assert_eq!(
+            expedition.totals().unwrap(),
+            [6000, 4000, 11000, 24000, 10000]
+        );
+        assert_eq!(expedition.top_calories(1).unwrap(), 24000);
+    }
+
+    #[test]
+    fn example_top_three() {
+        let expedition = read_expedition(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(
+            expedition.ranking().unwrap()[..3],
+            [
+                RankedElf {
+                    index: 3,
+                    total: 24000
+                },
+                RankedElf {
+                    index: 2,
+                    total: 11000
+                },
+                RankedElf {
+                    index: 4,
+                    total: 10000
+                },
+            ]
+        );
+        assert_eq!(expedition.top_calories(TOP_ELVES).unwrap(), 45000);
+        assert!(expedition.top_calories(6).is_err());
+    }
+
+    #[test]
+    fn example_statistics() {
+        let expedition = read_expedition(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(expedition.item_count(), 10);
+        assert_eq!(expedition.mean(), Some(11000.0));
+        assert_eq!(expedition.median(), Some(10000.0));
+        assert_eq!(expedition.percentile(0.0), Some(4000));
+        assert_eq!(expedition.percentile(40.0), Some(6000));
+        assert_eq!(expedition.percentile(100.0), Some(24000));
+        assert_eq!(expedition.percentile(101.0), None);
+        assert_eq!(
+            expedition.histogram(5000),
+            Some(Histogram {
+                start: 0,
+                width: 5000,
+                counts: vec![1, 1, 2, 0, 1],
+            })
+        );
+        assert_eq!(expedition.histogram(0), None);
+        assert_eq!(expedition.histogram(1), None);
+        assert_eq!(expedition.histogram(2), None);
+        assert_eq!(expedition.histogram(3).unwrap().counts.len(), 6668);
+    }
+
+    #[test]
+    fn empty_expedition_statistics() {
+        let expedition = Expedition::default();
+        assert_eq!(expedition.mean(), None);
+        assert_eq!(expedition.median(), None);
+        assert_eq!(expedition.percentile(50.0), None);
+        assert_eq!(expedition.histogram(10), None);
+    }
+
+    #[test]
+    fn last_group_without_trailing_blank_line() {
+        let expedition = read_expedition("100\n\n\n200\n300".as_bytes()).unwrap();
+        assert_eq!(expedition.totals().unwrap(), [100, 500]);
+        assert_eq!(expedition.median(), Some(300.0));
+    }
+
+    #[test]
+    fn overflowing_calories_are_rejected() {
+        let input = format!("{}\n1\n", u64::MAX);
+        assert!(matches!(
+            read_expedition(input.as_bytes()),
+            Err(AocError::Parse { line: 2, .. })
+        ));
+        let expedition = Expedition {
+            elves: vec![
+                Elf {
+                    items: vec![u64::MAX],
+                },
+                Elf { items: vec![1] },
+            ],
+        };
+        assert!(expedition.top_calories(2).is_err());
+    }
+
+    #[test]
+    fn invalid_calories_report_position() {
+        match read_expedition("100\n\n 2x0\n".as_bytes()) {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (3, 2)),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
 }
@@ -0,0 +1,364 @@
+// mod day10
+
+use std::{
+    fmt::{self, Display},
+    io::BufRead,
+};
+
+use super::Solution;
+use crate::error::AocError;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Input = Vec<Instruction>;
+    type Answer1 = i32;
+    type Answer2 = CrtImage;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Self::Input, AocError> {
+        read_program(reader)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Answer1, AocError> {
+        signal_strength(&register_values(input)?)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Self::Answer2, AocError> {
+        Ok(render(&register_values(input)?))
+    }
+}
+
+pub fn print_answer() -> Result<(), AocError> {
+    super::print_solution(&Day10)
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Instruction {
+    Noop,
+    Addx(i32),
+}
+
+pub const CRT_WIDTH: usize = 40;
+pub const CRT_HEIGHT: usize = 6;
+
+/// The lit (`#`) and dark (`.`) pixels drawn by the CRT, one string per row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CrtImage(pub Vec<String>);
+
+impl Display for CrtImage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.join("\n"))
+    }
+}
+
+pub fn read_program<R>(reader: R) -> Result<Vec<Instruction>, AocError>
+where
+    R: BufRead,
+{
+    reader
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Ok(l) if l.trim().is_empty()))
+        .map(|(line_idx, line)| {
+            let line = line?;
+            let parse_err =
+                |column, reason| AocError::parse(Day10::DAY, line_idx + 1, column, &line, reason);
+            match line.trim().split_once(' ') {
+                None if line.trim() == "noop" => Ok(Instruction::Noop),
+                Some(("addx", value)) => value
+                    .parse()
+                    .map(Instruction::Addx)
+                    .map_err(|_| parse_err(line.len() - value.len() + 1, "Invalid addx value")),
+                _ => Err(parse_err(1, "Unknown instruction")),
+            }
+        })
+        .collect()
+}
+
+/// Value of the X register during each cycle, starting with cycle 1 at index 0.
+pub fn register_values(program: &[Instruction]) -> Result<Vec<i32>, AocError> {
+    let mut x: i32 = 1;
+    let mut values = Vec::with_capacity(program.len() * 2);
+    for instruction in program {
+        match instruction {
+            Instruction::Noop => values.push(x),
+            Instruction::Addx(v) => {
+                values.extend([x, x]);
+                x = x.checked_add(*v).ok_or_else(|| {
+                    AocError::no_solution(Day10::DAY, format!("addx {} overflows X = {}", v, x))
+                })?;
+            }
+        }
+    }
+    Ok(values)
+}
+
+pub fn signal_strength(values: &[i32]) -> Result<i32, AocError> {
+    values
+        .iter()
+        .enumerate()
+        .skip(19)
+        .step_by(40)
+        .try_fold(0i32, |sum, (idx, x)| {
+            i32::try_from(idx + 1)
+                .ok()
+                .and_then(|cycle| cycle.checked_mul(*x))
+                .and_then(|strength| sum.checked_add(strength))
+        })
+        .ok_or_else(|| AocError::no_solution(Day10::DAY, "signal strength overflows i32"))
+}
+
+pub fn render(values: &[i32]) -> CrtImage {
+    CrtImage(
+        values
+            .chunks(CRT_WIDTH)
+            .take(CRT_HEIGHT)
+            .map(|row| {
+                row.iter()
+                    .enumerate()
+                    .map(|(pos, x)| {
+                        if (pos as i64 - i64::from(*x)).abs() <= 1 {
+                            '#'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect()
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "addx 15
+addx -11
+addx 6
+addx -3
+addx 5
+addx -1
+addx -8
+addx 13
+addx 4
+noop
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx -35
+addx 1
+addx 24
+addx -19
+addx 1
+addx 16
+addx -11
+noop
+noop
+addx 21
+addx -15
+noop
+noop
+addx -3
+addx 9
+addx 1
+addx -3
+addx 8
+addx 1
+addx 5
+noop
+noop
+noop
+noop
+noop
+addx -36
+noop
+addx 1
+addx 7
+noop
+noop
+noop
+addx 2
+addx 6
+noop
+noop
+noop
+noop
+noop
+addx 1
+noop
+noop
+addx 7
+addx 1
+noop
+addx -13
+addx 13
+addx 7
+noop
+addx 1
+addx -33
+noop
+noop
+noop
+addx 2
+noop
+noop
+noop
+addx 8
+noop
+addx -1
+addx 2
+addx 1
+noop
+addx 17
+addx -9
+addx 1
+addx 1
+addx -3
+addx 11
+noop
+noop
+addx 1
+noop
+addx 1
+noop
+noop
+addx -13
+addx -19
+addx 1
+addx 3
+addx 26
+addx -30
+addx 12
+addx -1
+addx 3
+addx 1
+noop
+noop
+noop
+addx -9
+addx 18
+addx 1
+addx 2
+noop
+noop
+addx 9
+noop
+noop
+noop
+addx -1
+addx 2
+addx -37
+addx 1
+addx 3
+noop
+addx 15
+addx -21
+addx 22
+addx -6
+addx 1
+noop
+addx 2
+addx 1
+noop
+addx -10
+noop
+noop
+addx 20
+addx 1
+addx 2
+addx 2
+addx -6
+addx -11
+noop
+noop
+noop
+";
+
+    #[test]
+    fn example_signal_strength() {
+        let program = read_program(EXAMPLE.as_bytes()).unwrap();
+        let values = register_values(&program).unwrap();
+        assert_eq!(values[19], 21);
+        assert_eq!(values[219], 18);
+        assert_eq!(Day10.part1(&program).unwrap(), 13140);
+    }
+
+    #[test]
+    fn example_image() {
+        let program = read_program(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(
+            Day10.part2(&program).unwrap().to_string(),
+            "\
+##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######....."
+        );
+    }
+
+    #[test]
+    fn register_overflow_is_no_solution() {
+        let program = read_program("addx 2147483647\naddx 1\n".as_bytes()).unwrap();
+        assert!(matches!(
+            register_values(&program),
+            Err(AocError::NoSolution { .. })
+        ));
+        let mut program = vec![Instruction::Addx(i32::MAX - 1)];
+        program.extend(vec![Instruction::Noop; 20]);
+        assert!(matches!(
+            signal_strength(&register_values(&program).unwrap()),
+            Err(AocError::NoSolution { .. })
+        ));
+    }
+
+    #[test]
+    fn small_program_register_values() {
+        let program = read_program("noop\naddx 3\naddx -5\n".as_bytes()).unwrap();
+        assert_eq!(
+            program,
+            [
+                Instruction::Noop,
+                Instruction::Addx(3),
+                Instruction::Addx(-5)
+            ]
+        );
+        assert_eq!(register_values(&program).unwrap(), [1, 1, 1, 4, 4]);
+    }
+
+    #[test]
+    fn signal_strength_samples_every_forty_cycles() {
+        let program = vec![Instruction::Noop; 240];
+        assert_eq!(
+            signal_strength(&register_values(&program).unwrap()).unwrap(),
+            20 + 60 + 100 + 140 + 180 + 220
+        );
+    }
+
+    #[test]
+    fn render_sprite_position() {
+        let mut program = vec![Instruction::Addx(2)];
+        program.extend(vec![Instruction::Noop; 238]);
+        let image = render(&register_values(&program).unwrap());
+        assert_eq!(image.0.len(), CRT_HEIGHT);
+        assert!(image.0[0].starts_with("#####..."));
+        assert!(image.0[5].starts_with("..###..."));
+    }
+
+    #[test]
+    fn unknown_instruction_is_error() {
+        assert!(matches!(
+            read_program("noop\nmulx 3\n".as_bytes()),
+            Err(AocError::Parse { line: 2, .. })
+        ));
+    }
+}
@@ -0,0 +1,346 @@
+// mod day11
+
+use std::io::BufRead;
+
+use super::Solution;
+use crate::error::AocError;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    type Input = Vec<Monkey>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Self::Input, AocError> {
+        read_monkeys(reader)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Answer1, AocError> {
+        monkey_business(input, 20, Relief::DivideBy(3))
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Self::Answer2, AocError> {
+        monkey_business(input, 10_000, Relief::None)
+    }
+}
+
+pub fn print_answer() -> Result<(), AocError> {
+    super::print_solution(&Day11)
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Operand {
+    Old,
+    Value(u64),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Operation {
+    Add(Operand),
+    Mul(Operand),
+}
+
+impl Operation {
+    /// The new worry level, or `None` if it does not fit in a `u64`.
+    fn apply(&self, old: u64) -> Option<u64> {
+        let value = |operand: &Operand| match operand {
+            Operand::Old => old,
+            Operand::Value(v) => *v,
+        };
+        match self {
+            Operation::Add(operand) => old.checked_add(value(operand)),
+            Operation::Mul(operand) => old.checked_mul(value(operand)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Monkey {
+    pub items: Vec<u64>,
+    pub operation: Operation,
+    pub divisor: u64,
+    pub if_true: usize,
+    pub if_false: usize,
+}
+
+/// How worry levels drop after a monkey inspects an item.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Relief {
+    None,
+    DivideBy(u64),
+}
+
+fn parse_monkey(lines: &[(usize, String)]) -> Result<Monkey, AocError> {
+    let field = |idx: usize, prefix: &str| -> Result<&str, AocError> {
+        let (line_idx, line) = lines.get(idx).ok_or_else(|| {
+            let (line_idx, line) = lines.last().unwrap();
+            AocError::parse(
+                Day11::DAY,
+                line_idx + 1,
+                1,
+                line,
+                "Incomplete monkey description",
+            )
+        })?;
+        let trimmed = line.trim_start();
+        trimmed.strip_prefix(prefix).ok_or_else(|| {
+            let column = line.len() - trimmed.len() + 1;
+            AocError::parse(
+                Day11::DAY,
+                line_idx + 1,
+                column,
+                line,
+                format!("Expected '{}'", prefix),
+            )
+        })
+    };
+    let number_err = |idx: usize, value: &str| {
+        let (line_idx, line) = &lines[idx];
+        let column = line.len() - value.len() + 1;
+        AocError::parse(Day11::DAY, line_idx + 1, column, line, "Invalid number")
+    };
+
+    field(0, "Monkey ")?;
+    let items = field(1, "Starting items:")?
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| item.parse().map_err(|_| number_err(1, item)))
+        .collect::<Result<Vec<u64>, AocError>>()?;
+
+    let operation = field(2, "Operation: new = old ")?;
+    let operand = match operation.get(2..) {
+        Some("old") => Operand::Old,
+        Some(value) => Operand::Value(value.parse().map_err(|_| number_err(2, value))?),
+        None => return Err(number_err(2, operation)),
+    };
+    let operation = match operation.chars().next() {
+        Some('+') => Operation::Add(operand),
+        Some('*') => Operation::Mul(operand),
+        _ => return Err(number_err(2, operation)),
+    };
+
+    let divisor = field(3, "Test: divisible by ")?;
+    let divisor = match divisor.parse() {
+        Ok(0) | Err(_) => return Err(number_err(3, divisor)),
+        Ok(d) => d,
+    };
+    let if_true = field(4, "If true: throw to monkey ")?;
+    let if_true = if_true.parse().map_err(|_| number_err(4, if_true))?;
+    let if_false = field(5, "If false: throw to monkey ")?;
+    let if_false = if_false.parse().map_err(|_| number_err(5, if_false))?;
+
+    Ok(Monkey {
+        items,
+        operation,
+        divisor,
+        if_true,
+        if_false,
+    })
+}
+
+pub fn read_monkeys<R>(reader: R) -> Result<Vec<Monkey>, AocError>
+where
+    R: BufRead,
+{
+    let mut monkeys = Vec::new();
+    let mut block = Vec::new();
+    for (line_idx, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            if !block.is_empty() {
+                monkeys.push(parse_monkey(&block)?);
+                block.clear();
+            }
+        } else {
+            block.push((line_idx, line));
+        }
+    }
+    if !block.is_empty() {
+        monkeys.push(parse_monkey(&block)?);
+    }
+
+    for (idx, monkey) in monkeys.iter().enumerate() {
+        let target = monkey.if_true.max(monkey.if_false);
+        if target >= monkeys.len() {
+            return Err(AocError::no_solution(
+                Day11::DAY,
+                format!("monkey {} throws to unknown monkey {}", idx, target),
+            ));
+        }
+    }
+    Ok(monkeys)
+}
+
+/// Number of items each monkey inspects over `rounds` rounds.
+pub fn inspections(
+    monkeys: &[Monkey],
+    rounds: usize,
+    relief: Relief,
+) -> Result<Vec<u64>, AocError> {
+    // Divisibility tests are preserved modulo the product of all divisors.
+    let modulus = monkeys
+        .iter()
+        .try_fold(1u64, |product, m| product.checked_mul(m.divisor));
+    let modulus = match relief {
+        Relief::None => modulus.ok_or_else(|| {
+            AocError::no_solution(Day11::DAY, "the product of the divisors overflows u64")
+        })?,
+        Relief::DivideBy(0) => {
+            return Err(AocError::no_solution(Day11::DAY, "relief divides by zero"))
+        }
+        // Only used without relief.
+        Relief::DivideBy(_) => 0,
+    };
+    let mut items = monkeys
+        .iter()
+        .map(|m| m.items.clone())
+        .collect::<Vec<Vec<u64>>>();
+    let mut counts = vec![0; monkeys.len()];
+
+    for _ in 0..rounds {
+        for (idx, monkey) in monkeys.iter().enumerate() {
+            let held = std::mem::take(&mut items[idx]);
+            counts[idx] += held.len() as u64;
+            for item in held {
+                let worry = monkey.operation.apply(item).ok_or_else(|| {
+                    AocError::no_solution(
+                        Day11::DAY,
+                        format!(
+                            "monkey {} overflows u64 inspecting worry level {}",
+                            idx, item
+                        ),
+                    )
+                })?;
+                let worry = match relief {
+                    Relief::None => worry % modulus,
+                    Relief::DivideBy(d) => worry / d,
+                };
+                let target = if worry % monkey.divisor == 0 {
+                    monkey.if_true
+                } else {
+                    monkey.if_false
+                };
+                items[target].push(worry);
+            }
+        }
+    }
+    Ok(counts)
+}
+
+pub fn monkey_business(monkeys: &[Monkey], rounds: usize, relief: Relief) -> Result<u64, AocError> {
+    let mut counts = inspections(monkeys, rounds, relief)?;
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+    counts
+        .iter()
+        .take(2)
+        .try_fold(1u64, |product, &count| product.checked_mul(count))
+        .ok_or_else(|| AocError::no_solution(Day11::DAY, "monkey business overflows u64"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1
+";
+
+    #[test]
+    fn example_parse() {
+        let monkeys = read_monkeys(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(monkeys.len(), 4);
+        assert_eq!(
+            monkeys[2],
+            Monkey {
+                items: vec![79, 60, 97],
+                operation: Operation::Mul(Operand::Old),
+                divisor: 13,
+                if_true: 1,
+                if_false: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn example_inspections() {
+        let monkeys = read_monkeys(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(
+            inspections(&monkeys, 20, Relief::DivideBy(3)).unwrap(),
+            [101, 95, 7, 105]
+        );
+        assert_eq!(
+            inspections(&monkeys, 10_000, Relief::None).unwrap(),
+            [52166, 47830, 1938, 52013]
+        );
+    }
+
+    #[test]
+    fn example_monkey_business() {
+        let monkeys = read_monkeys(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(
+            monkey_business(&monkeys, 20, Relief::DivideBy(3)).unwrap(),
+            10605
+        );
+        assert_eq!(
+            monkey_business(&monkeys, 10_000, Relief::None).unwrap(),
+            2713310158
+        );
+    }
+
+    #[test]
+    fn overflow_is_no_solution() {
+        let input = EXAMPLE.replace("79, 98", &u64::MAX.to_string());
+        let monkeys = read_monkeys(input.as_bytes()).unwrap();
+        assert!(matches!(
+            inspections(&monkeys, 1, Relief::DivideBy(3)),
+            Err(AocError::NoSolution { .. })
+        ));
+
+        let input = EXAMPLE
+            .replace("by 23", "by 4294967311")
+            .replace("by 19", "by 4294967357");
+        let monkeys = read_monkeys(input.as_bytes()).unwrap();
+        assert!(inspections(&monkeys, 20, Relief::DivideBy(3)).is_ok());
+        assert!(matches!(
+            inspections(&monkeys, 1, Relief::None),
+            Err(AocError::NoSolution { .. })
+        ));
+    }
+
+    #[test]
+    fn invalid_operation_reports_line() {
+        let input = EXAMPLE.replace("old * 19", "old ^ 19");
+        assert!(matches!(
+            read_monkeys(input.as_bytes()),
+            Err(AocError::Parse { line: 3, .. })
+        ));
+    }
+}
@@ -0,0 +1,181 @@
+// mod day12
+
+use std::{collections::VecDeque, io::BufRead};
+
+use super::Solution;
+use crate::error::AocError;
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    type Input = Heightmap;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Self::Input, AocError> {
+        read_heightmap(reader)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Answer1, AocError> {
+        let distances = input.distances_to_end();
+        distances[input.start.0][input.start.1]
+            .ok_or_else(|| AocError::no_solution(Self::DAY, "no path from S to E"))
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Self::Answer2, AocError> {
+        let distances = input.distances_to_end();
+        input
+            .heights
+            .iter()
+            .flatten()
+            .zip(distances.iter().flatten())
+            .filter_map(|(height, distance)| if *height == 0 { *distance } else { None })
+            .min()
+            .ok_or_else(|| AocError::no_solution(Self::DAY, "no path from elevation a to E"))
+    }
+}
+
+pub fn print_answer() -> Result<(), AocError> {
+    super::print_solution(&Day12)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Heightmap {
+    /// Elevation per square, `a` = 0 through `z` = 25.
+    pub heights: Vec<Vec<u8>>,
+    pub start: (usize, usize),
+    pub end: (usize, usize),
+}
+
+impl Heightmap {
+    fn neighbours(&self, (r, c): (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        [(-1, 0), (1, 0), (0, -1), (0, 1)]
+            .into_iter()
+            .filter_map(move |(dr, dc)| {
+                let nr = r.checked_add_signed(dr)?;
+                let nc = c.checked_add_signed(dc)?;
+                self.heights.get(nr)?.get(nc).map(|_| (nr, nc))
+            })
+    }
+
+    /// Fewest steps from every square to the end, walking the climbing rules backwards.
+    pub fn distances_to_end(&self) -> Vec<Vec<Option<usize>>> {
+        let mut distances = self
+            .heights
+            .iter()
+            .map(|row| vec![None; row.len()])
+            .collect::<Vec<Vec<Option<usize>>>>();
+        distances[self.end.0][self.end.1] = Some(0);
+        let mut queue = VecDeque::from([(self.end, 0)]);
+
+        while let Some((pos, distance)) = queue.pop_front() {
+            let height = self.heights[pos.0][pos.1];
+            for next in self.neighbours(pos) {
+                if distances[next.0][next.1].is_none() && height <= self.heights[next.0][next.1] + 1
+                {
+                    distances[next.0][next.1] = Some(distance + 1);
+                    queue.push_back((next, distance + 1));
+                }
+            }
+        }
+        distances
+    }
+}
+
+pub fn read_heightmap<R>(reader: R) -> Result<Heightmap, AocError>
+where
+    R: BufRead,
+{
+    let mut heights = Vec::new();
+    let mut start = None;
+    let mut end = None;
+    for (line_idx, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let row = line
+            .bytes()
+            .enumerate()
+            .map(|(col, square)| match square {
+                b'a'..=b'z' => Ok(square - b'a'),
+                b'S' => {
+                    start = Some((heights.len(), col));
+                    Ok(0)
+                }
+                b'E' => {
+                    end = Some((heights.len(), col));
+                    Ok(25)
+                }
+                _ => Err(AocError::parse(
+                    Day12::DAY,
+                    line_idx + 1,
+                    col + 1,
+                    &line,
+                    "Invalid elevation",
+                )),
+            })
+            .collect::<Result<Vec<u8>, AocError>>()?;
+        heights.push(row);
+    }
+
+    match (start, end) {
+        (Some(start), Some(end)) => Ok(Heightmap {
+            heights,
+            start,
+            end,
+        }),
+        _ => Err(AocError::no_solution(
+            Day12::DAY,
+            "heightmap needs both S and E",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Sabqponm
+abcryxxl
+accszExk
+acctuvwj
+abdefghi
+";
+
+    #[test]
+    fn example_parse() {
+        let map = read_heightmap(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(map.start, (0, 0));
+        assert_eq!(map.end, (2, 5));
+    }
+
+    #[test]
+    fn example_answers() {
+        let map = Day12.parse(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(Day12.part1(&map).unwrap(), 31);
+        assert_eq!(Day12.part2(&map).unwrap(), 29);
+    }
+
+    #[test]
+    fn unreachable_end_is_no_solution() {
+        let map = read_heightmap("Saz\nbbE\n".as_bytes()).unwrap();
+        assert!(matches!(
+            Day12.part1(&map),
+            Err(AocError::NoSolution { .. })
+        ));
+    }
+
+    #[test]
+    fn invalid_square_reports_position() {
+        assert!(matches!(
+            read_heightmap("Sab\na1E\n".as_bytes()),
+            Err(AocError::Parse {
+                line: 2,
+                column: 2,
+                ..
+            })
+        ));
+    }
+}
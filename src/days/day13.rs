@@ -0,0 +1,218 @@
+// mod day13
+
+use std::{cmp::Ordering, io::BufRead, str::FromStr};
+
+use super::Solution;
+use crate::error::AocError;
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    type Input = Vec<Packet>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Self::Input, AocError> {
+        read_packets(reader)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Answer1, AocError> {
+        Ok(ordered_pairs(input))
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Self::Answer2, AocError> {
+        Ok(decoder_key(input))
+    }
+}
+
+pub fn print_answer() -> Result<(), AocError> {
+    super::print_solution(&Day13)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Packet {
+    Int(u32),
+    List(Vec<Packet>),
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Packet::Int(a), Packet::Int(b)) => a.cmp(b),
+            (Packet::List(a), Packet::List(b)) => a.cmp(b),
+            (Packet::Int(a), Packet::List(b)) => [Packet::Int(*a)][..].cmp(b),
+            (Packet::List(a), Packet::Int(b)) => a[..].cmp(&[Packet::Int(*b)]),
+        }
+    }
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Byte offset of the failure within the packet text, and why it failed.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PacketError(pub usize, pub &'static str);
+
+fn parse_packet(bytes: &[u8], pos: &mut usize) -> Result<Packet, PacketError> {
+    match bytes.get(*pos) {
+        Some(b'[') => {
+            *pos += 1;
+            let mut items = Vec::new();
+            if bytes.get(*pos) == Some(&b']') {
+                *pos += 1;
+                return Ok(Packet::List(items));
+            }
+            loop {
+                items.push(parse_packet(bytes, pos)?);
+                match bytes.get(*pos) {
+                    Some(b',') => *pos += 1,
+                    Some(b']') => {
+                        *pos += 1;
+                        return Ok(Packet::List(items));
+                    }
+                    _ => return Err(PacketError(*pos, "Expected ',' or ']'")),
+                }
+            }
+        }
+        Some(b'0'..=b'9') => {
+            let start = *pos;
+            while bytes.get(*pos).is_some_and(u8::is_ascii_digit) {
+                *pos += 1;
+            }
+            std::str::from_utf8(&bytes[start..*pos])
+                .ok()
+                .and_then(|digits| digits.parse().ok())
+                .map(Packet::Int)
+                .ok_or(PacketError(start, "Integer out of range"))
+        }
+        _ => Err(PacketError(*pos, "Expected '[' or an integer")),
+    }
+}
+
+impl FromStr for Packet {
+    type Err = PacketError;
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut pos = 0;
+        let packet = parse_packet(value.as_bytes(), &mut pos)?;
+        if pos != value.len() {
+            return Err(PacketError(pos, "Unexpected trailing data"));
+        }
+        Ok(packet)
+    }
+}
+
+/// All packets in input order, skipping the blank lines between pairs.
+pub fn read_packets<R>(reader: R) -> Result<Vec<Packet>, AocError>
+where
+    R: BufRead,
+{
+    reader
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Ok(l) if l.trim().is_empty()))
+        .map(|(line_idx, line)| {
+            let line = line?;
+            Packet::from_str(line.trim_end()).map_err(|PacketError(offset, reason)| {
+                AocError::parse(Day13::DAY, line_idx + 1, offset + 1, &line, reason)
+            })
+        })
+        .collect()
+}
+
+/// Sum of the 1-based indices of the pairs that are in the right order.
+pub fn ordered_pairs(packets: &[Packet]) -> usize {
+    packets
+        .chunks(2)
+        .enumerate()
+        .filter(|(_, pair)| matches!(pair, [left, right] if left < right))
+        .map(|(idx, _)| idx + 1)
+        .sum()
+}
+
+pub fn decoder_key(packets: &[Packet]) -> usize {
+    let dividers = [
+        Packet::List(vec![Packet::List(vec![Packet::Int(2)])]),
+        Packet::List(vec![Packet::List(vec![Packet::Int(6)])]),
+    ];
+    // Position of a divider in the sorted list is one plus the number of smaller packets.
+    dividers
+        .iter()
+        .enumerate()
+        .map(|(idx, divider)| idx + 1 + packets.iter().filter(|p| *p < divider).count())
+        .product()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "[1,1,3,1,1]
+[1,1,5,1,1]
+
+[[1],[2,3,4]]
+[[1],4]
+
+[9]
+[[8,7,6]]
+
+[[4,4],4,4]
+[[4,4],4,4,4]
+
+[7,7,7,7]
+[7,7,7]
+
+[]
+[3]
+
+[[[]]]
+[[]]
+
+[1,[2,[3,[4,[5,6,7]]]],8,9]
+[1,[2,[3,[4,[5,6,0]]]],8,9]
+";
+
+    #[test]
+    fn example_ordered_pairs() {
+        let packets = read_packets(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(packets.len(), 16);
+        assert_eq!(ordered_pairs(&packets), 13);
+    }
+
+    #[test]
+    fn example_decoder_key() {
+        let packets = read_packets(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(decoder_key(&packets), 140);
+    }
+
+    #[test]
+    fn parse_packet_text() {
+        assert_eq!(
+            Packet::from_str("[10,[]]"),
+            Ok(Packet::List(vec![Packet::Int(10), Packet::List(vec![])]))
+        );
+        assert_eq!(
+            Packet::from_str("[1,2"),
+            Err(PacketError(4, "Expected ',' or ']'"))
+        );
+        assert_eq!(
+            Packet::from_str("[1]]"),
+            Err(PacketError(3, "Unexpected trailing data"))
+        );
+    }
+
+    #[test]
+    fn invalid_packet_reports_position() {
+        assert!(matches!(
+            read_packets("[1]\n[1,x]\n".as_bytes()),
+            Err(AocError::Parse {
+                line: 2,
+                column: 4,
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,174 @@
+// mod day14
+
+use std::{collections::HashSet, io::BufRead};
+
+use super::Solution;
+use crate::error::AocError;
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    type Input = Cave;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Self::Input, AocError> {
+        read_cave(reader)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Answer1, AocError> {
+        Ok(input.clone().pour_sand(Floor::Abyss))
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Self::Answer2, AocError> {
+        Ok(input.clone().pour_sand(Floor::Solid))
+    }
+}
+
+pub fn print_answer() -> Result<(), AocError> {
+    super::print_solution(&Day14)
+}
+
+pub const SAND_SOURCE: (i32, i32) = (500, 0);
+
+/// What lies two units below the lowest rock.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Floor {
+    Abyss,
+    Solid,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cave {
+    blocked: HashSet<(i32, i32)>,
+    lowest_rock: i32,
+}
+
+impl Cave {
+    pub fn from_paths(paths: &[Vec<(i32, i32)>]) -> Self {
+        let mut blocked = HashSet::new();
+        for path in paths {
+            for segment in path.windows(2) {
+                let ((x1, y1), (x2, y2)) = (segment[0], segment[1]);
+                for x in x1.min(x2)..=x1.max(x2) {
+                    for y in y1.min(y2)..=y1.max(y2) {
+                        blocked.insert((x, y));
+                    }
+                }
+            }
+            if let [single] = path[..] {
+                blocked.insert(single);
+            }
+        }
+        let lowest_rock = blocked.iter().map(|(_, y)| *y).max().unwrap_or(0);
+        Cave {
+            blocked,
+            lowest_rock,
+        }
+    }
+
+    /// Position where the next unit of sand comes to rest, or `None` if it falls forever.
+    fn drop_sand(&self, floor: Floor) -> Option<(i32, i32)> {
+        let (mut x, mut y) = SAND_SOURCE;
+        loop {
+            if y > self.lowest_rock {
+                return match floor {
+                    Floor::Abyss => None,
+                    Floor::Solid => Some((x, y)),
+                };
+            }
+            match [x, x - 1, x + 1]
+                .into_iter()
+                .find(|nx| !self.blocked.contains(&(*nx, y + 1)))
+            {
+                Some(nx) => (x, y) = (nx, y + 1),
+                None => return Some((x, y)),
+            }
+        }
+    }
+
+    /// Units of sand that come to rest before sand falls into the abyss or blocks the source.
+    pub fn pour_sand(&mut self, floor: Floor) -> usize {
+        let mut units = 0;
+        while !self.blocked.contains(&SAND_SOURCE) {
+            match self.drop_sand(floor) {
+                Some(rest) => {
+                    self.blocked.insert(rest);
+                    units += 1;
+                }
+                None => break,
+            }
+        }
+        units
+    }
+}
+
+pub fn read_cave<R>(reader: R) -> Result<Cave, AocError>
+where
+    R: BufRead,
+{
+    let paths = reader
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Ok(l) if l.trim().is_empty()))
+        .map(|(line_idx, line)| {
+            let line = line?;
+            let mut offset = 0;
+            line.split(" -> ")
+                .map(|point| {
+                    let column = offset + 1;
+                    offset += point.len() + 4;
+                    point
+                        .trim()
+                        .split_once(',')
+                        .and_then(|(x, y)| Some((x.parse().ok()?, y.parse().ok()?)))
+                        .ok_or_else(|| {
+                            AocError::parse(
+                                Day14::DAY,
+                                line_idx + 1,
+                                column,
+                                &line,
+                                "Expected 'x,y'",
+                            )
+                        })
+                })
+                .collect::<Result<Vec<(i32, i32)>, AocError>>()
+        })
+        .collect::<Result<Vec<Vec<(i32, i32)>>, AocError>>()?;
+    Ok(Cave::from_paths(&paths))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9
+";
+
+    #[test]
+    fn example_abyss() {
+        let mut cave = read_cave(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(cave.lowest_rock, 9);
+        assert_eq!(cave.pour_sand(Floor::Abyss), 24);
+    }
+
+    #[test]
+    fn example_floor() {
+        let mut cave = read_cave(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(cave.pour_sand(Floor::Solid), 93);
+    }
+
+    #[test]
+    fn invalid_point_reports_column() {
+        assert!(matches!(
+            read_cave("498,4 -> 498;6\n".as_bytes()),
+            Err(AocError::Parse {
+                line: 1,
+                column: 10,
+                ..
+            })
+        ));
+    }
+}
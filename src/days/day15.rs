@@ -0,0 +1,204 @@
+// mod day15
+
+use std::{collections::HashSet, io::BufRead};
+
+use super::Solution;
+use crate::error::AocError;
+
+pub struct Day15;
+
+pub const TARGET_ROW: i64 = 2_000_000;
+pub const SEARCH_LIMIT: i64 = 4_000_000;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    type Input = Vec<Sensor>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Self::Input, AocError> {
+        read_sensors(reader)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Answer1, AocError> {
+        Ok(excluded_positions(input, TARGET_ROW))
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Self::Answer2, AocError> {
+        find_distress_beacon(input, SEARCH_LIMIT)
+            .map(|(x, y)| x * 4_000_000 + y)
+            .ok_or_else(|| AocError::no_solution(Self::DAY, "no uncovered position in range"))
+    }
+}
+
+pub fn print_answer() -> Result<(), AocError> {
+    super::print_solution(&Day15)
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Sensor {
+    pub position: (i64, i64),
+    pub beacon: (i64, i64),
+}
+
+impl Sensor {
+    pub fn radius(&self) -> i64 {
+        manhattan(self.position, self.beacon)
+    }
+
+    pub fn covers(&self, point: (i64, i64)) -> bool {
+        manhattan(self.position, point) <= self.radius()
+    }
+
+    /// Inclusive x-range covered on row `y`, if any.
+    pub fn row_coverage(&self, y: i64) -> Option<(i64, i64)> {
+        let reach = self.radius() - (self.position.1 - y).abs();
+        (reach >= 0).then(|| (self.position.0 - reach, self.position.0 + reach))
+    }
+}
+
+fn manhattan(a: (i64, i64), b: (i64, i64)) -> i64 {
+    (a.0 - b.0).abs() + (a.1 - b.1).abs()
+}
+
+pub fn read_sensors<R>(reader: R) -> Result<Vec<Sensor>, AocError>
+where
+    R: BufRead,
+{
+    const PARTS: [&str; 4] = ["Sensor at x=", ", y=", ": closest beacon is at x=", ", y="];
+    reader
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Ok(l) if l.trim().is_empty()))
+        .map(|(line_idx, line)| {
+            let line = line?;
+            let mut rest = line.as_str();
+            let mut values = [0i64; 4];
+            for (value, prefix) in values.iter_mut().zip(PARTS) {
+                let column = line.len() - rest.len() + 1;
+                let parse_err =
+                    |reason| AocError::parse(Day15::DAY, line_idx + 1, column, &line, reason);
+                rest = rest
+                    .strip_prefix(prefix)
+                    .ok_or_else(|| parse_err(format!("Expected '{}'", prefix)))?;
+                let end = rest
+                    .find(|c: char| c != '-' && !c.is_ascii_digit())
+                    .unwrap_or(rest.len());
+                *value = rest[..end].parse().map_err(|_| {
+                    let column = line.len() - rest.len() + 1;
+                    AocError::parse(
+                        Day15::DAY,
+                        line_idx + 1,
+                        column,
+                        &line,
+                        "Invalid coordinate",
+                    )
+                })?;
+                rest = &rest[end..];
+            }
+            Ok(Sensor {
+                position: (values[0], values[1]),
+                beacon: (values[2], values[3]),
+            })
+        })
+        .collect()
+}
+
+fn merged_coverage(sensors: &[Sensor], y: i64) -> Vec<(i64, i64)> {
+    let mut ranges = sensors
+        .iter()
+        .filter_map(|s| s.row_coverage(y))
+        .collect::<Vec<(i64, i64)>>();
+    ranges.sort_unstable();
+    ranges.into_iter().fold(Vec::new(), |mut merged, (lo, hi)| {
+        match merged.last_mut() {
+            Some((_, last_hi)) if lo <= *last_hi + 1 => *last_hi = hi.max(*last_hi),
+            _ => merged.push((lo, hi)),
+        }
+        merged
+    })
+}
+
+/// Positions on row `y` where no beacon can be.
+pub fn excluded_positions(sensors: &[Sensor], y: i64) -> i64 {
+    let beacons_on_row = sensors
+        .iter()
+        .filter(|s| s.beacon.1 == y)
+        .map(|s| s.beacon)
+        .collect::<HashSet<(i64, i64)>>()
+        .len() as i64;
+    merged_coverage(sensors, y)
+        .iter()
+        .map(|(lo, hi)| hi - lo + 1)
+        .sum::<i64>()
+        - beacons_on_row
+}
+
+/// The single position within `0..=limit` on both axes that no sensor covers.
+pub fn find_distress_beacon(sensors: &[Sensor], limit: i64) -> Option<(i64, i64)> {
+    // A lone uncovered point lies just outside several sensor diamonds, so it sits on the
+    // intersection of their boundary lines `y = x + a` and `y = -x + b`.
+    let mut rising = Vec::new();
+    let mut falling = Vec::new();
+    for s in sensors {
+        let (x, y, r) = (s.position.0, s.position.1, s.radius() + 1);
+        rising.extend([y - x + r, y - x - r]);
+        falling.extend([y + x + r, y + x - r]);
+    }
+
+    let corners = [(0, 0), (0, limit), (limit, 0), (limit, limit)];
+    let intersections = rising.iter().flat_map(|a| {
+        falling
+            .iter()
+            .filter(move |b| (*b - a) % 2 == 0)
+            .map(move |b| ((b - a) / 2, (a + b) / 2))
+    });
+    corners
+        .into_iter()
+        .chain(intersections)
+        .filter(|(x, y)| (0..=limit).contains(x) && (0..=limit).contains(y))
+        .find(|point| sensors.iter().all(|s| !s.covers(*point)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3
+";
+
+    #[test]
+    fn example_excluded_positions() {
+        let sensors = read_sensors(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(sensors[0].position, (2, 18));
+        assert_eq!(sensors[0].beacon, (-2, 15));
+        assert_eq!(excluded_positions(&sensors, 10), 26);
+    }
+
+    #[test]
+    fn example_distress_beacon() {
+        let sensors = read_sensors(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(find_distress_beacon(&sensors, 20), Some((14, 11)));
+    }
+
+    #[test]
+    fn invalid_sensor_reports_column() {
+        assert!(matches!(
+            read_sensors("Sensor at x=2, y=1a: closest beacon is at x=0, y=0\n".as_bytes()),
+            Err(AocError::Parse { column: 19, .. })
+        ));
+    }
+}
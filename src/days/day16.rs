@@ -0,0 +1,296 @@
+// mod day16
+
+use std::{
+    collections::{HashMap, VecDeque},
+    io::BufRead,
+};
+
+use super::Solution;
+use crate::error::AocError;
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    type Input = Network;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Self::Input, AocError> {
+        read_network(reader)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Answer1, AocError> {
+        Ok(input.max_pressure(30))
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Self::Answer2, AocError> {
+        Ok(input.max_pressure_with_elephant(26))
+    }
+}
+
+pub fn print_answer() -> Result<(), AocError> {
+    super::print_solution(&Day16)
+}
+
+pub const START_VALVE: &str = "AA";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Valve {
+    pub name: String,
+    pub flow_rate: u32,
+    pub tunnels: Vec<String>,
+}
+
+/// The valves worth opening, with travel times between them.
+#[derive(Clone, Debug)]
+pub struct Network {
+    /// Flow rate of every valve with a positive rate.
+    pub flow_rates: Vec<u32>,
+    /// Minutes to walk from the start valve to each useful valve.
+    pub from_start: Vec<u32>,
+    /// Minutes to walk between useful valves.
+    pub distances: Vec<Vec<u32>>,
+}
+
+fn parse_valve(line: &str) -> Result<Valve, (usize, &'static str)> {
+    let rest = line.strip_prefix("Valve ").ok_or((0, "Expected 'Valve'"))?;
+    let (name, rest) = rest
+        .split_once(" has flow rate=")
+        .ok_or((6, "Expected 'has flow rate='"))?;
+    let (rate, tunnels) = rest
+        .split_once(';')
+        .ok_or((line.len() - rest.len(), "Expected ';'"))?;
+    let flow_rate = rate
+        .parse()
+        .map_err(|_| (line.len() - rest.len(), "Invalid flow rate"))?;
+    let tunnels = [" tunnels lead to valves ", " tunnel leads to valve "]
+        .iter()
+        .find_map(|prefix| tunnels.strip_prefix(prefix))
+        .ok_or((line.len() - tunnels.len(), "Expected tunnel list"))?;
+    Ok(Valve {
+        name: name.to_owned(),
+        flow_rate,
+        tunnels: tunnels.split(", ").map(str::to_owned).collect(),
+    })
+}
+
+pub fn read_valves<R>(reader: R) -> Result<Vec<Valve>, AocError>
+where
+    R: BufRead,
+{
+    reader
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Ok(l) if l.trim().is_empty()))
+        .map(|(line_idx, line)| {
+            let line = line?;
+            parse_valve(line.trim_end()).map_err(|(offset, reason)| {
+                AocError::parse(Day16::DAY, line_idx + 1, offset + 1, &line, reason)
+            })
+        })
+        .collect()
+}
+
+pub fn read_network<R>(reader: R) -> Result<Network, AocError>
+where
+    R: BufRead,
+{
+    Network::new(&read_valves(reader)?)
+}
+
+impl Network {
+    pub fn new(valves: &[Valve]) -> Result<Self, AocError> {
+        let index = valves
+            .iter()
+            .enumerate()
+            .map(|(idx, v)| (v.name.as_str(), idx))
+            .collect::<HashMap<&str, usize>>();
+        let neighbours = valves
+            .iter()
+            .map(|v| {
+                v.tunnels
+                    .iter()
+                    .map(|t| {
+                        index.get(t.as_str()).copied().ok_or_else(|| {
+                            AocError::no_solution(
+                                Day16::DAY,
+                                format!("valve {} leads to unknown valve {}", v.name, t),
+                            )
+                        })
+                    })
+                    .collect::<Result<Vec<usize>, AocError>>()
+            })
+            .collect::<Result<Vec<Vec<usize>>, AocError>>()?;
+        let start = *index.get(START_VALVE).ok_or_else(|| {
+            AocError::no_solution(Day16::DAY, format!("no start valve {}", START_VALVE))
+        })?;
+
+        let walk = |from: usize| {
+            let mut dist = vec![u32::MAX; valves.len()];
+            dist[from] = 0;
+            let mut queue = VecDeque::from([from]);
+            while let Some(v) = queue.pop_front() {
+                for &n in &neighbours[v] {
+                    if dist[n] == u32::MAX {
+                        dist[n] = dist[v] + 1;
+                        queue.push_back(n);
+                    }
+                }
+            }
+            dist
+        };
+
+        let useful = (0..valves.len())
+            .filter(|idx| valves[*idx].flow_rate > 0)
+            .collect::<Vec<usize>>();
+        if useful.len() > 63 {
+            return Err(AocError::no_solution(
+                Day16::DAY,
+                "more than 63 valves with positive flow",
+            ));
+        }
+        let start_dist = walk(start);
+        Ok(Network {
+            flow_rates: useful.iter().map(|idx| valves[*idx].flow_rate).collect(),
+            from_start: useful.iter().map(|idx| start_dist[*idx]).collect(),
+            distances: useful
+                .iter()
+                .map(|from| {
+                    let dist = walk(*from);
+                    useful.iter().map(|to| dist[*to]).collect()
+                })
+                .collect(),
+        })
+    }
+
+    /// Best released pressure for every set of opened valves reachable within `minutes`.
+    fn best_by_opened(&self, minutes: u32) -> HashMap<u64, u32> {
+        let mut best = HashMap::new();
+        let mut stack = self
+            .from_start
+            .iter()
+            .enumerate()
+            .filter(|(_, d)| **d < minutes)
+            .map(|(valve, d)| {
+                let remaining = minutes - d - 1;
+                (
+                    valve,
+                    remaining,
+                    1u64 << valve,
+                    remaining * self.flow_rates[valve],
+                )
+            })
+            .collect::<Vec<_>>();
+        best.insert(0, 0);
+
+        while let Some((valve, remaining, opened, released)) = stack.pop() {
+            let entry = best.entry(opened).or_insert(0);
+            *entry = (*entry).max(released);
+            for (next, d) in self.distances[valve].iter().enumerate() {
+                if opened & (1 << next) == 0 && *d < remaining {
+                    let left = remaining - d - 1;
+                    stack.push((
+                        next,
+                        left,
+                        opened | 1 << next,
+                        released + left * self.flow_rates[next],
+                    ));
+                }
+            }
+        }
+        best
+    }
+
+    pub fn max_pressure(&self, minutes: u32) -> u32 {
+        self.best_by_opened(minutes)
+            .values()
+            .copied()
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Best combined pressure when two workers split the valves between them.
+    pub fn max_pressure_with_elephant(&self, minutes: u32) -> u32 {
+        let best = self.best_by_opened(minutes);
+        let mut by_pressure = best.into_iter().collect::<Vec<(u64, u32)>>();
+        by_pressure.sort_unstable_by_key(|v| std::cmp::Reverse(v.1));
+
+        let mut result = 0;
+        for (idx, (mine, pressure)) in by_pressure.iter().enumerate() {
+            if pressure * 2 < result {
+                break;
+            }
+            if let Some((_, other)) = by_pressure[idx..]
+                .iter()
+                .find(|(theirs, _)| mine & theirs == 0)
+            {
+                result = result.max(pressure + other);
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II
+";
+
+    #[test]
+    fn example_parse() {
+        let valves = read_valves(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(
+            valves[7],
+            Valve {
+                name: "HH".to_owned(),
+                flow_rate: 22,
+                tunnels: vec!["GG".to_owned()],
+            }
+        );
+        let network = Network::new(&valves).unwrap();
+        assert_eq!(network.flow_rates, [13, 2, 20, 3, 22, 21]);
+        assert_eq!(network.from_start, [1, 2, 1, 2, 5, 2]);
+    }
+
+    #[test]
+    fn example_max_pressure() {
+        let network = read_network(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(network.max_pressure(30), 1651);
+    }
+
+    #[test]
+    fn example_with_elephant() {
+        let network = read_network(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(network.max_pressure_with_elephant(26), 1707);
+    }
+
+    #[test]
+    fn unknown_tunnel_is_error() {
+        let input = "Valve AA has flow rate=0; tunnel leads to valve ZZ\n";
+        assert!(matches!(
+            read_network(input.as_bytes()),
+            Err(AocError::NoSolution { .. })
+        ));
+    }
+
+    #[test]
+    fn invalid_rate_reports_column() {
+        let input = "Valve AA has flow rate=x; tunnel leads to valve AA\n";
+        assert!(matches!(
+            read_network(input.as_bytes()),
+            Err(AocError::Parse { column: 24, .. })
+        ));
+    }
+}
@@ -0,0 +1,199 @@
+// mod day17
+
+use std::{collections::HashMap, io::BufRead};
+
+use super::Solution;
+use crate::error::AocError;
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    type Input = Vec<Jet>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Self::Input, AocError> {
+        read_jets(reader)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Answer1, AocError> {
+        Ok(tower_height(input, 2022))
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Self::Answer2, AocError> {
+        Ok(tower_height(input, 1_000_000_000_000))
+    }
+}
+
+pub fn print_answer() -> Result<(), AocError> {
+    super::print_solution(&Day17)
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Jet {
+    Left,
+    Right,
+}
+
+/// Rock shapes as row bitmasks, bottom row first, already offset two units from the left
+/// wall. Bit 6 is the leftmost column of the chamber.
+const ROCKS: [&[u8]; 5] = [
+    &[0b0011110],
+    &[0b0001000, 0b0011100, 0b0001000],
+    &[0b0011100, 0b0000100, 0b0000100],
+    &[0b0010000, 0b0010000, 0b0010000, 0b0010000],
+    &[0b0011000, 0b0011000],
+];
+
+/// Rows at the top of the tower compared when looking for a repeating state.
+const PROFILE_DEPTH: usize = 32;
+
+pub fn read_jets<R>(reader: R) -> Result<Vec<Jet>, AocError>
+where
+    R: BufRead,
+{
+    let mut jets = Vec::new();
+    for (line_idx, line) in reader.lines().enumerate() {
+        let line = line?;
+        for (col, c) in line.trim_end().chars().enumerate() {
+            jets.push(match c {
+                '<' => Jet::Left,
+                '>' => Jet::Right,
+                _ => {
+                    return Err(AocError::parse(
+                        Day17::DAY,
+                        line_idx + 1,
+                        col + 1,
+                        &line,
+                        "Expected '<' or '>'",
+                    ))
+                }
+            });
+        }
+    }
+    if jets.is_empty() {
+        return Err(AocError::no_solution(Day17::DAY, "empty jet pattern"));
+    }
+    Ok(jets)
+}
+
+struct Chamber<'a> {
+    rows: Vec<u8>,
+    jets: &'a [Jet],
+    jet_idx: usize,
+}
+
+impl Chamber<'_> {
+    fn collides(&self, rock: &[u8], y: usize) -> bool {
+        rock.iter()
+            .enumerate()
+            .any(|(dy, row)| self.rows.get(y + dy).is_some_and(|r| r & row != 0))
+    }
+
+    fn drop_rock(&mut self, shape: &[u8]) {
+        let mut rock = shape.to_vec();
+        let mut y = self.rows.len() + 3;
+        loop {
+            let jet = self.jets[self.jet_idx];
+            self.jet_idx = (self.jet_idx + 1) % self.jets.len();
+            let pushed = match jet {
+                Jet::Left if rock.iter().all(|r| r & 0b1000000 == 0) => {
+                    Some(rock.iter().map(|r| r << 1).collect::<Vec<u8>>())
+                }
+                Jet::Right if rock.iter().all(|r| r & 0b0000001 == 0) => {
+                    Some(rock.iter().map(|r| r >> 1).collect::<Vec<u8>>())
+                }
+                _ => None,
+            };
+            if let Some(pushed) = pushed {
+                if !self.collides(&pushed, y) {
+                    rock = pushed;
+                }
+            }
+            if y == 0 || self.collides(&rock, y - 1) {
+                break;
+            }
+            y -= 1;
+        }
+
+        for (dy, row) in rock.iter().enumerate() {
+            match self.rows.get_mut(y + dy) {
+                Some(r) => *r |= row,
+                None => self.rows.push(*row),
+            }
+        }
+    }
+
+    fn profile(&self) -> Vec<u8> {
+        self.rows
+            .iter()
+            .rev()
+            .take(PROFILE_DEPTH)
+            .copied()
+            .collect()
+    }
+}
+
+/// Height of the tower after `rocks` rocks have come to rest.
+pub fn tower_height(jets: &[Jet], rocks: u64) -> u64 {
+    let mut chamber = Chamber {
+        rows: Vec::new(),
+        jets,
+        jet_idx: 0,
+    };
+    let mut seen = HashMap::new();
+    let mut skipped_height = 0;
+    let mut rock = 0;
+
+    while rock < rocks {
+        chamber.drop_rock(ROCKS[(rock % ROCKS.len() as u64) as usize]);
+        rock += 1;
+
+        if skipped_height == 0 {
+            let key = (
+                rock % ROCKS.len() as u64,
+                chamber.jet_idx,
+                chamber.profile(),
+            );
+            let height = chamber.rows.len() as u64;
+            if let Some((prev_rock, prev_height)) = seen.insert(key, (rock, height)) {
+                let period = rock - prev_rock;
+                let cycles = (rocks - rock) / period;
+                skipped_height = cycles * (height - prev_height);
+                rock += cycles * period;
+            }
+        }
+    }
+    chamber.rows.len() as u64 + skipped_height
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>\n";
+
+    #[test]
+    fn example_short_tower() {
+        let jets = read_jets(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(jets.len(), 40);
+        assert_eq!(tower_height(&jets, 1), 1);
+        assert_eq!(tower_height(&jets, 2), 4);
+        assert_eq!(tower_height(&jets, 2022), 3068);
+    }
+
+    #[test]
+    fn example_tall_tower() {
+        let jets = read_jets(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(tower_height(&jets, 1_000_000_000_000), 1514285714288);
+    }
+
+    #[test]
+    fn invalid_jet_reports_column() {
+        assert!(matches!(
+            read_jets("<<>x>\n".as_bytes()),
+            Err(AocError::Parse { column: 4, .. })
+        ));
+    }
+}
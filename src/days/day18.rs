@@ -0,0 +1,192 @@
+// mod day18
+
+use std::{collections::HashSet, io::BufRead};
+
+use super::Solution;
+use crate::error::AocError;
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+    type Input = HashSet<Cube>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Self::Input, AocError> {
+        read_cubes(reader)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Answer1, AocError> {
+        Ok(surface_area(input))
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Self::Answer2, AocError> {
+        Ok(exterior_surface_area(input))
+    }
+}
+
+pub fn print_answer() -> Result<(), AocError> {
+    super::print_solution(&Day18)
+}
+
+pub type Cube = (i32, i32, i32);
+
+/// A cube position widened so that neighbours of cubes at the edge of the `i32` range exist.
+type Cell = (i64, i64, i64);
+
+fn widen(cubes: &HashSet<Cube>) -> HashSet<Cell> {
+    cubes
+        .iter()
+        .map(|&(x, y, z)| (x.into(), y.into(), z.into()))
+        .collect()
+}
+
+fn neighbours((x, y, z): Cell) -> [Cell; 6] {
+    [
+        (x - 1, y, z),
+        (x + 1, y, z),
+        (x, y - 1, z),
+        (x, y + 1, z),
+        (x, y, z - 1),
+        (x, y, z + 1),
+    ]
+}
+
+pub fn read_cubes<R>(reader: R) -> Result<HashSet<Cube>, AocError>
+where
+    R: BufRead,
+{
+    reader
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Ok(l) if l.trim().is_empty()))
+        .map(|(line_idx, line)| {
+            let line = line?;
+            let mut offset = 0;
+            let coords = line
+                .trim_end()
+                .split(',')
+                .map(|value| {
+                    let column = offset + 1;
+                    offset += value.len() + 1;
+                    value.trim().parse::<i32>().map_err(|_| {
+                        AocError::parse(
+                            Day18::DAY,
+                            line_idx + 1,
+                            column,
+                            &line,
+                            "Invalid coordinate",
+                        )
+                    })
+                })
+                .collect::<Result<Vec<i32>, AocError>>()?;
+            match coords[..] {
+                [x, y, z] => Ok((x, y, z)),
+                _ => Err(AocError::parse(
+                    Day18::DAY,
+                    line_idx + 1,
+                    1,
+                    &line,
+                    "Expected three coordinates",
+                )),
+            }
+        })
+        .collect()
+}
+
+pub fn surface_area(cubes: &HashSet<Cube>) -> usize {
+    let cubes = widen(cubes);
+    cubes
+        .iter()
+        .flat_map(|cube| neighbours(*cube))
+        .filter(|n| !cubes.contains(n))
+        .count()
+}
+
+/// Surface reachable by steam flowing around the droplet, excluding trapped air pockets.
+pub fn exterior_surface_area(cubes: &HashSet<Cube>) -> usize {
+    let cubes = widen(cubes);
+    let (Some(min), Some(max)) = (
+        cubes.iter().map(|c| c.0.min(c.1).min(c.2)).min(),
+        cubes.iter().map(|c| c.0.max(c.1).max(c.2)).max(),
+    ) else {
+        return 0;
+    };
+    let bounds = (min - 1)..=(max + 1);
+    let inside = |(x, y, z): &Cell| bounds.contains(x) && bounds.contains(y) && bounds.contains(z);
+
+    let start = (min - 1, min - 1, min - 1);
+    let mut steam = HashSet::from([start]);
+    let mut stack = vec![start];
+    let mut faces = 0;
+    while let Some(cell) = stack.pop() {
+        for n in neighbours(cell) {
+            if cubes.contains(&n) {
+                faces += 1;
+            } else if inside(&n) && steam.insert(n) {
+                stack.push(n);
+            }
+        }
+    }
+    faces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "2,2,2
+1,2,2
+3,2,2
+2,1,2
+2,3,2
+2,2,1
+2,2,3
+2,2,4
+2,2,6
+1,2,5
+3,2,5
+2,1,5
+2,3,5
+";
+
+    #[test]
+    fn two_adjacent_cubes() {
+        let cubes = HashSet::from([(1, 1, 1), (2, 1, 1)]);
+        assert_eq!(surface_area(&cubes), 10);
+    }
+
+    #[test]
+    fn example_surface_area() {
+        let cubes = read_cubes(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(surface_area(&cubes), 64);
+        assert_eq!(exterior_surface_area(&cubes), 58);
+    }
+
+    #[test]
+    fn cubes_at_the_edge_of_the_range() {
+        for edge in [i32::MIN, i32::MAX] {
+            let next = if edge == i32::MIN { edge + 1 } else { edge - 1 };
+            let cubes = HashSet::from([(edge, edge, edge), (next, edge, edge)]);
+            assert_eq!(surface_area(&cubes), 10);
+            assert_eq!(exterior_surface_area(&cubes), 10);
+        }
+    }
+
+    #[test]
+    fn invalid_coordinate_reports_column() {
+        assert!(matches!(
+            read_cubes("1,2,3\n1,x,3\n".as_bytes()),
+            Err(AocError::Parse {
+                line: 2,
+                column: 3,
+                ..
+            })
+        ));
+        assert!(matches!(
+            read_cubes("1,2\n".as_bytes()),
+            Err(AocError::Parse { line: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,197 @@
+// mod day19
+
+use std::io::BufRead;
+
+use super::Solution;
+use crate::error::AocError;
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+    type Input = Vec<Blueprint>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Self::Input, AocError> {
+        read_blueprints(reader)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Answer1, AocError> {
+        Ok(input.iter().map(|bp| bp.id * max_geodes(bp, 24)).sum())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Self::Answer2, AocError> {
+        Ok(input.iter().take(3).map(|bp| max_geodes(bp, 32)).product())
+    }
+}
+
+pub fn print_answer() -> Result<(), AocError> {
+    super::print_solution(&Day19)
+}
+
+const ORE: usize = 0;
+const CLAY: usize = 1;
+const OBSIDIAN: usize = 2;
+const GEODE: usize = 3;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Blueprint {
+    pub id: u32,
+    /// Cost of each robot type in ore, clay and obsidian, indexed ore, clay, obsidian, geode.
+    pub costs: [[u32; 3]; 4],
+}
+
+impl Blueprint {
+    /// No point in producing more of a resource per minute than any single robot costs.
+    fn max_useful(&self) -> [u32; 3] {
+        let mut max = [0; 3];
+        for cost in &self.costs {
+            for (m, c) in max.iter_mut().zip(cost) {
+                *m = (*m).max(*c);
+            }
+        }
+        max
+    }
+}
+
+pub fn read_blueprints<R>(reader: R) -> Result<Vec<Blueprint>, AocError>
+where
+    R: BufRead,
+{
+    reader
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Ok(l) if l.trim().is_empty()))
+        .map(|(line_idx, line)| {
+            let line = line?;
+            let numbers = line
+                .split(|c: char| !c.is_ascii_digit())
+                .filter(|n| !n.is_empty())
+                .map(|n| n.parse::<u32>())
+                .collect::<Result<Vec<u32>, _>>();
+            match numbers.as_deref() {
+                Ok(&[id, ore, clay, obsidian_ore, obsidian_clay, geode_ore, geode_obsidian]) => {
+                    Ok(Blueprint {
+                        id,
+                        costs: [
+                            [ore, 0, 0],
+                            [clay, 0, 0],
+                            [obsidian_ore, obsidian_clay, 0],
+                            [geode_ore, 0, geode_obsidian],
+                        ],
+                    })
+                }
+                _ => Err(AocError::parse(
+                    Day19::DAY,
+                    line_idx + 1,
+                    1,
+                    &line,
+                    "Expected a blueprint id and six robot costs",
+                )),
+            }
+        })
+        .collect()
+}
+
+struct Search<'a> {
+    blueprint: &'a Blueprint,
+    max_useful: [u32; 3],
+    best: u32,
+}
+
+impl Search<'_> {
+    fn explore(&mut self, time: u32, robots: [u32; 3], resources: [u32; 3], geodes: u32) {
+        self.best = self.best.max(geodes);
+        // Even a new geode robot every remaining minute could not beat the best so far.
+        if geodes + time * time.saturating_sub(1) / 2 <= self.best {
+            return;
+        }
+
+        for robot in [GEODE, OBSIDIAN, CLAY, ORE] {
+            if robot != GEODE && robots[robot] >= self.max_useful[robot] {
+                continue;
+            }
+            let cost = self.blueprint.costs[robot];
+            let wait = (0..3).try_fold(0, |wait, r| {
+                if cost[r] <= resources[r] {
+                    Some(wait)
+                } else if robots[r] == 0 {
+                    None
+                } else {
+                    Some(wait.max((cost[r] - resources[r]).div_ceil(robots[r])))
+                }
+            });
+            let Some(wait) = wait else { continue };
+            if wait + 1 >= time {
+                continue;
+            }
+
+            let remaining = time - wait - 1;
+            let next_resources: [u32; 3] =
+                std::array::from_fn(|r| resources[r] + robots[r] * (wait + 1) - cost[r]);
+            if robot == GEODE {
+                self.explore(remaining, robots, next_resources, geodes + remaining);
+            } else {
+                let mut next_robots = robots;
+                next_robots[robot] += 1;
+                self.explore(remaining, next_robots, next_resources, geodes);
+            }
+        }
+    }
+}
+
+/// Most geodes that can be opened in `minutes` starting with a single ore robot.
+pub fn max_geodes(blueprint: &Blueprint, minutes: u32) -> u32 {
+    let mut search = Search {
+        blueprint,
+        max_useful: blueprint.max_useful(),
+        best: 0,
+    };
+    search.explore(minutes, [1, 0, 0], [0, 0, 0], 0);
+    search.best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
+Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.
+";
+
+    #[test]
+    fn example_parse() {
+        let blueprints = read_blueprints(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(
+            blueprints[0],
+            Blueprint {
+                id: 1,
+                costs: [[4, 0, 0], [2, 0, 0], [3, 14, 0], [2, 0, 7]],
+            }
+        );
+    }
+
+    #[test]
+    fn example_quality_levels() {
+        let blueprints = read_blueprints(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(max_geodes(&blueprints[0], 24), 9);
+        assert_eq!(max_geodes(&blueprints[1], 24), 12);
+        assert_eq!(Day19.part1(&blueprints).unwrap(), 33);
+    }
+
+    #[test]
+    fn example_longer_search() {
+        let blueprints = read_blueprints(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(max_geodes(&blueprints[0], 32), 56);
+        assert_eq!(max_geodes(&blueprints[1], 32), 62);
+    }
+
+    #[test]
+    fn incomplete_blueprint_is_error() {
+        assert!(matches!(
+            read_blueprints("Blueprint 1: Each ore robot costs 4 ore.\n".as_bytes()),
+            Err(AocError::Parse { line: 1, .. })
+        ));
+    }
+}
@@ -1,103 +1,173 @@
 // mod day2
 
-use std::fs::File;
+use std::fmt::{self, Display};
 use std::io::BufRead;
-use std::io::BufReader;
 use std::ops::Add;
+use std::sync::OnceLock;
 
-pub fn print_answer() {
-    println!(
-        "{:?}",
-        read_strategy_guide(BufReader::new(File::open("data/input_day2").unwrap()))
-    );
+use super::Solution;
+use crate::error::AocError;
+
+pub mod strategy;
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+    type Input = Guide;
+    type Answer1 = Score;
+    type Answer2 = Score;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Self::Input, AocError> {
+        read_strategy_guide(reader, Rules::classic())
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Answer1, AocError> {
+        play(input, GuideInterpretation::AsShape).map(|result| result.total)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Self::Answer2, AocError> {
+        play(input, GuideInterpretation::AsOutcome).map(|result| result.total)
+    }
 }
 
-#[derive(Copy, Clone)]
-enum Shape {
-    Rock = 1,
-    Paper = 2,
-    Scissors = 3,
+pub fn print_answer() -> Result<(), AocError> {
+    super::print_solution(&Day2)
 }
 
-#[derive(Copy, Clone)]
-enum Outcome {
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
     Win = 6,
     Draw = 3,
     Lose = 0,
 }
 
+/// The shapes of classic rock-paper-scissors, a typed view of [`Rules::classic`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Shape {
+    Rock = 1,
+    Paper = 2,
+    Scissors = 3,
+}
+
+/// Classic shapes in the cycle order of [`Rules::classic`].
+const CLASSIC_CYCLE: [Shape; 3] = [Shape::Rock, Shape::Scissors, Shape::Paper];
+
+fn classic_rules() -> &'static Rules {
+    static CLASSIC: OnceLock<Rules> = OnceLock::new();
+    CLASSIC.get_or_init(Rules::classic)
+}
+
 impl Shape {
+    /// Index of this shape in [`Rules::classic`].
+    pub fn index(self) -> usize {
+        CLASSIC_CYCLE
+            .iter()
+            .position(|s| *s == self)
+            .expect("every shape is in the classic cycle")
+    }
+
+    /// The shape at `index` in [`Rules::classic`].
+    pub fn from_index(index: usize) -> Option<Self> {
+        CLASSIC_CYCLE.get(index).copied()
+    }
+
     pub fn plays(&self, other: Self) -> Outcome {
-        match self {
-            Self::Rock => match other {
-                Self::Rock => Outcome::Draw,
-                Self::Scissors => Outcome::Win,
-                Self::Paper => Outcome::Lose,
-            },
-            Self::Paper => match other {
-                Self::Paper => Outcome::Draw,
-                Self::Rock => Outcome::Win,
-                Self::Scissors => Outcome::Lose,
-            },
-            Self::Scissors => match other {
-                Self::Scissors => Outcome::Draw,
-                Self::Paper => Outcome::Win,
-                Self::Rock => Outcome::Lose,
-            },
-        }
+        classic_rules().outcome(self.index(), other.index())
     }
 
-    pub fn self_from_outcome(result: Outcome, opponent: Self) -> Self{
-        match opponent {
-            Self::Rock => match result {
-                Outcome::Draw => Self::Rock,
-                Outcome::Win => Self::Paper,
-                Outcome::Lose => Self::Scissors,
-            },
-            Self::Paper => match result {
-                Outcome::Draw => Self::Paper,
-                Outcome::Win => Self::Scissors,
-                Outcome::Lose => Self::Rock,
-            },
-            Self::Scissors => match result {
-                Outcome::Draw => Self::Scissors,
-                Outcome::Win => Self::Rock,
-                Outcome::Lose => Self::Paper,
-            },
-        }
+    pub fn self_from_outcome(result: Outcome, opponent: Self) -> Self {
+        let own = classic_rules().respond(result, opponent.index());
+        Self::from_index(own).expect("classic rules respond with a classic shape")
     }
 }
 
-#[derive(Copy, Clone, Debug)]
-struct Score(u32);
-
 impl TryFrom<char> for Shape {
     type Error = &'static str;
     fn try_from(letter: char) -> Result<Self, Self::Error> {
-        match letter {
-            'A' | 'X' => Ok(Self::Rock),
-            'B' | 'Y' => Ok(Self::Paper),
-            'C' | 'Z' => Ok(Self::Scissors),
-            _ => Err("Cannot decrypt into Shape from given letter"),
+        let rules = classic_rules();
+        rules
+            .opponent_shape(letter)
+            .or_else(|| rules.own_shape(letter))
+            .and_then(Self::from_index)
+            .ok_or("Cannot decrypt into Shape from given letter")
+    }
+}
+
+/// Outcomes in the order of [`CIPHER_LETTERS`].
+pub const OUTCOMES: [Outcome; 3] = [Outcome::Lose, Outcome::Draw, Outcome::Win];
+
+/// The letters of a guide's second column that mean lose, draw and win when it lists outcomes.
+pub const CIPHER_LETTERS: [char; 3] = ['X', 'Y', 'Z'];
+
+/// How the second column of the guide is decrypted.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GuideInterpretation {
+    /// The letter is the own letter of the shape to play (part 1).
+    AsShape,
+    /// The letter is one of [`CIPHER_LETTERS`], the outcome to reach (part 2).
+    AsOutcome,
+}
+
+impl GuideInterpretation {
+    /// Index of the shape to play against shape `opponent` for the second-column `letter`, or
+    /// `None` if the letter means nothing under this interpretation.
+    pub fn decode(&self, rules: &Rules, opponent: usize, letter: char) -> Option<usize> {
+        match self {
+            Self::AsShape => rules.own_shape(letter),
+            Self::AsOutcome => {
+                let result = CIPHER_LETTERS.iter().position(|c| *c == letter)?;
+                Some(rules.respond(OUTCOMES[result], opponent))
+            }
         }
     }
 }
 
-impl TryFrom<char> for Outcome {
-    type Error = &'static str;
-    fn try_from(letter: char) -> Result<Self, Self::Error> {
-        match letter {
-            'X' => Ok(Outcome::Lose),
-            'Y' => Ok(Outcome::Draw),
-            'Z' => Ok(Outcome::Win),
-            _ => Err("Cannot decrypt into Outcome from given letter"),
+/// One decrypted round and the score it earns, with shapes as indices into the [`Rules`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Round {
+    pub opponent: usize,
+    pub own: usize,
+    pub outcome: Outcome,
+    pub score: Score,
+}
+
+impl Round {
+    pub fn new(rules: &Rules, opponent: usize, own: usize) -> Self {
+        Round {
+            opponent,
+            own,
+            outcome: rules.outcome(own, opponent),
+            score: rules.round_score(own, opponent),
         }
     }
 }
 
+/// Every round of a guide played under one interpretation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GuideResult {
+    pub rounds: Vec<Round>,
+    pub total: Score,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Score(u32);
+
+impl Score {
+    pub fn value(&self) -> u32 {
+        self.0
+    }
+}
+
+impl Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 impl From<Shape> for Score {
     fn from(value: Shape) -> Self {
-        Self(value as u32)
+        Self(classic_rules().shapes()[value.index()].score)
     }
 }
 
@@ -114,22 +184,475 @@ impl Add for Score {
     }
 }
 
-fn read_strategy_guide<R>(reader: R) -> Score
+fn read_letter_pairs<R, A, B, FA, FB>(
+    reader: R,
+    decode_a: FA,
+    decode_b: FB,
+) -> Result<Vec<(A, B)>, AocError>
 where
     R: BufRead,
+    FA: Fn(char) -> Result<A, &'static str>,
+    FB: Fn(char) -> Result<B, &'static str>,
 {
-    reader
-        .lines()
-        .flat_map(|line| match line.unwrap().trim().as_bytes() {
-            [a, _, b] => Some((char::try_from(*a).ok(), char::try_from(*b).ok())),
-            _ => None,
-        })
-        .flat_map(|parse_tuple| match parse_tuple {
-            (Some(a), Some(b)) => Some((Shape::try_from(a).unwrap(), Outcome::try_from(b).unwrap())),
-            _ => None,
-        })
-        .map(|(opponent, result)|  (opponent, Shape::self_from_outcome(result, opponent)))
-        .fold(Score(0), |score, game| {
-            score + game.1.plays(game.0).into() + game.1.into()
+    let mut pairs = Vec::new();
+    for (line_idx, line) in reader.lines().enumerate() {
+        let line = line?;
+        let offset = line.len() - line.trim_start().len();
+        let parse_err = |column, reason| {
+            AocError::parse(Day2::DAY, line_idx + 1, offset + column, &line, reason)
+        };
+        let chars = line.trim().chars().collect::<Vec<char>>();
+        let pair = match chars[..] {
+            [] => continue,
+            [a, ' ', b] => (
+                decode_a(a).map_err(|e| parse_err(1, e))?,
+                decode_b(b).map_err(|e| parse_err(3, e))?,
+            ),
+            _ => return Err(parse_err(1, "Expected two letters separated by a space")),
+        };
+        pairs.push(pair);
+    }
+    Ok(pairs)
+}
+
+/// A strategy guide read against the [`Rules`] whose letters it uses.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Guide {
+    pub rules: Rules,
+    /// The opponent's shape index and the second-column letter of each round.
+    pub rounds: Vec<(usize, char)>,
+}
+
+/// Parses a guide of `<opponent letter> <letter>` rounds, where the second letter is an own
+/// letter of `rules` or one of [`CIPHER_LETTERS`].
+pub fn read_strategy_guide<R>(reader: R, rules: Rules) -> Result<Guide, AocError>
+where
+    R: BufRead,
+{
+    let rounds = read_letter_pairs(
+        reader,
+        |c| rules.opponent_shape(c).ok_or("Unknown opponent letter"),
+        |c| {
+            if rules.own_shape(c).is_some() || CIPHER_LETTERS.contains(&c) {
+                Ok(c)
+            } else {
+                Err("Unknown own or outcome letter")
+            }
+        },
+    )?;
+    Ok(Guide { rules, rounds })
+}
+
+/// Plays every round of `guide` decrypted with `interpretation`.
+pub fn play(guide: &Guide, interpretation: GuideInterpretation) -> Result<GuideResult, AocError> {
+    let rounds = guide
+        .rounds
+        .iter()
+        .enumerate()
+        .map(|(idx, (opponent, letter))| {
+            let own = interpretation
+                .decode(&guide.rules, *opponent, *letter)
+                .ok_or_else(|| {
+                    AocError::no_solution(
+                        Day2::DAY,
+                        format!(
+                            "round {}: '{}' is not {}",
+                            idx + 1,
+                            letter,
+                            match interpretation {
+                                GuideInterpretation::AsShape => "an own letter",
+                                GuideInterpretation::AsOutcome => "an outcome letter",
+                            }
+                        ),
+                    )
+                })?;
+            Ok(Round::new(&guide.rules, *opponent, own))
         })
-}
\ No newline at end of file
+        .collect::<Result<Vec<Round>, AocError>>()?;
+    let total = rounds
+        .iter()
+        .fold(Score(0), |total, round| total + round.score);
+    Ok(GuideResult { rounds, total })
+}
+
+/// Classic rock-paper-scissors in the rules file format.
+pub const RPS_RULES: &str = "\
+# name     score  opponent  own
+Rock       1      A         X
+Scissors   3      C         Z
+Paper      2      B         Y
+";
+
+/// Rock-paper-scissors-lizard-Spock.
+pub const RPSLS_RULES: &str = "\
+Rock       1  A  V
+Scissors   3  C  X
+Lizard     4  D  Y
+Paper      2  B  W
+Spock      5  E  Z
+";
+
+/// RPS-15: each shape beats the seven that follow it.
+pub const RPS15_RULES: &str = "\
+Rock       1   A  a
+Fire       2   B  b
+Scissors   3   C  c
+Snake      4   D  d
+Human      5   E  e
+Tree       6   F  f
+Wolf       7   G  g
+Sponge     8   H  h
+Paper      9   I  i
+Air        10  J  j
+Water      11  K  k
+Dragon     12  L  l
+Devil      13  M  m
+Lightning  14  N  n
+Gun        15  O  o
+";
+
+/// One shape of a [`Rules`] variant and the letters encrypting it in a guide.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShapeRule {
+    pub name: String,
+    pub score: u32,
+    pub opponent: char,
+    pub own: char,
+}
+
+/// A rock-paper-scissors variant with an odd number of shapes in a dominance cycle, where each
+/// shape beats the `(n - 1) / 2` shapes listed after it, wrapping around.
+///
+/// Rules files have one `name score opponent-letter own-letter` line per shape, in cycle
+/// order; blank lines and `#` comments are ignored.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rules {
+    shapes: Vec<ShapeRule>,
+}
+
+impl Rules {
+    pub fn parse(text: &str) -> Result<Rules, AocError> {
+        let mut shapes: Vec<ShapeRule> = Vec::new();
+        let mut last_line = 0;
+        for (line_idx, line) in text.lines().enumerate() {
+            let content = line.split('#').next().unwrap_or("");
+            if content.trim().is_empty() {
+                continue;
+            }
+            last_line = line_idx + 1;
+            let parse_err =
+                |column, reason| AocError::parse(Day2::DAY, line_idx + 1, column, line, reason);
+            let fields = content.split_whitespace().collect::<Vec<&str>>();
+            let column = |field: &str| field.as_ptr() as usize - line.as_ptr() as usize + 1;
+            let [name, score, opponent, own] = fields[..] else {
+                return Err(parse_err(
+                    1,
+                    "Expected name, score, opponent letter and own letter",
+                ));
+            };
+            let score = score
+                .parse()
+                .map_err(|_| parse_err(column(score), "Invalid score"))?;
+            let letter = |field: &str| {
+                let mut chars = field.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Ok(c),
+                    _ => Err(parse_err(column(field), "Expected a single letter")),
+                }
+            };
+            let (opponent_letter, own_letter) = (letter(opponent)?, letter(own)?);
+            if shapes.iter().any(|s| s.name == name) {
+                return Err(parse_err(column(name), "Duplicate shape name"));
+            }
+            if shapes.iter().any(|s| s.opponent == opponent_letter) {
+                return Err(parse_err(column(opponent), "Duplicate opponent letter"));
+            }
+            if shapes.iter().any(|s| s.own == own_letter) {
+                return Err(parse_err(column(own), "Duplicate own letter"));
+            }
+            shapes.push(ShapeRule {
+                name: name.to_owned(),
+                score,
+                opponent: opponent_letter,
+                own: own_letter,
+            });
+        }
+
+        if shapes.len() < 3 || shapes.len().is_multiple_of(2) {
+            let line = text.lines().nth(last_line.saturating_sub(1)).unwrap_or("");
+            return Err(AocError::parse(
+                Day2::DAY,
+                last_line.max(1),
+                1,
+                line,
+                "Rules need an odd number of at least three shapes",
+            ));
+        }
+        Ok(Rules { shapes })
+    }
+
+    /// The rules of [`RPS_RULES`], built directly since they are known to be valid.
+    pub fn classic() -> Rules {
+        let shapes = CLASSIC_CYCLE
+            .iter()
+            .map(|shape| {
+                let (name, opponent, own) = match shape {
+                    Shape::Rock => ("Rock", 'A', 'X'),
+                    Shape::Paper => ("Paper", 'B', 'Y'),
+                    Shape::Scissors => ("Scissors", 'C', 'Z'),
+                };
+                ShapeRule {
+                    name: name.to_owned(),
+                    score: *shape as u32,
+                    opponent,
+                    own,
+                }
+            })
+            .collect();
+        Rules { shapes }
+    }
+
+    pub fn shapes(&self) -> &[ShapeRule] {
+        &self.shapes
+    }
+
+    pub fn len(&self) -> usize {
+        self.shapes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shapes.is_empty()
+    }
+
+    /// Index of the shape called `name`.
+    pub fn find(&self, name: &str) -> Option<usize> {
+        self.shapes.iter().position(|s| s.name == name)
+    }
+
+    /// Index of the shape encrypted as `letter` in the opponent's column.
+    pub fn opponent_shape(&self, letter: char) -> Option<usize> {
+        self.shapes.iter().position(|s| s.opponent == letter)
+    }
+
+    /// Index of the shape encrypted as `letter` in the own column.
+    pub fn own_shape(&self, letter: char) -> Option<usize> {
+        self.shapes.iter().position(|s| s.own == letter)
+    }
+
+    /// Outcome for shape index `own` against shape index `other`.
+    pub fn outcome(&self, own: usize, other: usize) -> Outcome {
+        let n = self.len();
+        match (other + n - own) % n {
+            0 => Outcome::Draw,
+            d if d <= (n - 1) / 2 => Outcome::Win,
+            _ => Outcome::Lose,
+        }
+    }
+
+    /// A shape reaching `result` against `opponent`; the nearest one in the cycle when several do.
+    pub fn respond(&self, result: Outcome, opponent: usize) -> usize {
+        let n = self.len();
+        match result {
+            Outcome::Draw => opponent,
+            Outcome::Win => (opponent + n - 1) % n,
+            Outcome::Lose => (opponent + 1) % n,
+        }
+    }
+
+    pub fn round_score(&self, own: usize, other: usize) -> Score {
+        Score(self.shapes[own].score) + self.outcome(own, other).into()
+    }
+}
+
+/// Reads a rules file; see [`Rules`] for the format.
+pub fn read_rules<R>(mut reader: R) -> Result<Rules, AocError>
+where
+    R: BufRead,
+{
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    Rules::parse(&text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub(super) const EXAMPLE: &str = "A Y
+B X
+C Z
+";
+
+    pub(super) fn classic_guide(text: &str) -> Guide {
+        read_strategy_guide(text.as_bytes(), Rules::classic()).unwrap()
+    }
+
+    #[test]
+    fn example_score_as_shape() {
+        let guide = classic_guide(EXAMPLE);
+        let result = play(&guide, GuideInterpretation::AsShape).unwrap();
+        assert_eq!(
+            result.rounds[0],
+            Round {
+                opponent: Shape::Rock.index(),
+                own: Shape::Paper.index(),
+                outcome: Outcome::Win,
+                score: Score(8),
+            }
+        );
+        assert_eq!(
+            result
+                .rounds
+                .iter()
+                .map(|r| r.score)
+                .collect::<Vec<Score>>(),
+            [Score(8), Score(1), Score(6)]
+        );
+        assert_eq!(result.total, Score(15));
+    }
+
+    #[test]
+    fn example_score_by_outcome() {
+        let guide = classic_guide(EXAMPLE);
+        let rock = Shape::Rock.index();
+        let result = play(&guide, GuideInterpretation::AsOutcome).unwrap();
+        assert_eq!(
+            result.rounds.iter().map(|r| r.own).collect::<Vec<usize>>(),
+            [rock, rock, rock]
+        );
+        assert_eq!(
+            result
+                .rounds
+                .iter()
+                .map(|r| r.score)
+                .collect::<Vec<Score>>(),
+            [Score(4), Score(1), Score(7)]
+        );
+        assert_eq!(result.total, Score(12));
+    }
+
+    #[test]
+    fn shape_outcome_roundtrip() {
+        for opponent in [Shape::Rock, Shape::Paper, Shape::Scissors] {
+            for result in OUTCOMES {
+                let own = Shape::self_from_outcome(result, opponent);
+                assert_eq!(own.plays(opponent), result);
+            }
+        }
+        assert_eq!(Shape::Paper.plays(Shape::Rock), Outcome::Win);
+        assert_eq!(Shape::try_from('C'), Ok(Shape::Scissors));
+        assert_eq!(Shape::try_from('Y'), Ok(Shape::Paper));
+        assert!(Shape::try_from('D').is_err());
+    }
+
+    #[test]
+    fn invalid_letter_reports_column() {
+        match read_strategy_guide("A Y\nB Q\n".as_bytes(), Rules::classic()) {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (2, 3)),
+            other => panic!("unexpected result {:?}", other),
+        }
+        assert!(matches!(
+            read_strategy_guide("A Y\nX Y\n".as_bytes(), Rules::classic()),
+            Err(AocError::Parse {
+                line: 2,
+                column: 1,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn classic_rules_match_rules_file() {
+        let rules = Rules::classic();
+        assert_eq!(rules, Rules::parse(RPS_RULES).unwrap());
+        for own in [Shape::Rock, Shape::Paper, Shape::Scissors] {
+            assert_eq!(rules.find(&format!("{:?}", own)), Some(own.index()));
+            assert_eq!(Score::from(own), Score(own as u32));
+            for other in [Shape::Rock, Shape::Paper, Shape::Scissors] {
+                assert_eq!(rules.outcome(own.index(), other.index()), own.plays(other));
+            }
+        }
+        assert_eq!(
+            rules.round_score(Shape::Paper.index(), Shape::Rock.index()),
+            Score(8)
+        );
+    }
+
+    #[test]
+    fn lizard_spock_rules() {
+        let rules = read_rules(RPSLS_RULES.as_bytes()).unwrap();
+        let shape = |name| rules.find(name).unwrap();
+        assert_eq!(rules.outcome(shape("Spock"), shape("Rock")), Outcome::Win);
+        assert_eq!(
+            rules.outcome(shape("Spock"), shape("Scissors")),
+            Outcome::Win
+        );
+        assert_eq!(rules.outcome(shape("Lizard"), shape("Spock")), Outcome::Win);
+        assert_eq!(rules.outcome(shape("Lizard"), shape("Rock")), Outcome::Lose);
+        assert_eq!(rules.outcome(shape("Paper"), shape("Paper")), Outcome::Draw);
+
+        let guide = read_strategy_guide("E V\nA Z\n".as_bytes(), rules.clone()).unwrap();
+        assert_eq!(guide.rounds, [(shape("Spock"), 'V'), (shape("Rock"), 'Z')]);
+        let result = play(&guide, GuideInterpretation::AsShape).unwrap();
+        assert_eq!(
+            result.rounds.iter().map(|r| r.own).collect::<Vec<usize>>(),
+            [shape("Rock"), shape("Spock")]
+        );
+        assert_eq!(result.total, Score(1 + 5 + 6));
+        // V is Rock's own letter but means no outcome.
+        assert!(matches!(
+            play(&guide, GuideInterpretation::AsOutcome),
+            Err(AocError::NoSolution { .. })
+        ));
+
+        let guide = read_strategy_guide("A X\nA Z\n".as_bytes(), rules.clone()).unwrap();
+        let result = play(&guide, GuideInterpretation::AsOutcome).unwrap();
+        assert_eq!(
+            result.rounds.iter().map(|r| r.own).collect::<Vec<usize>>(),
+            [shape("Scissors"), shape("Spock")]
+        );
+        assert_eq!(result.total, Score(3 + 5 + 6));
+    }
+
+    #[test]
+    fn every_shape_beats_half_of_rps15() {
+        let rules = Rules::parse(RPS15_RULES).unwrap();
+        for own in 0..rules.len() {
+            let wins = (0..rules.len())
+                .filter(|other| rules.outcome(own, *other) == Outcome::Win)
+                .count();
+            assert_eq!(wins, 7);
+            for result in [Outcome::Win, Outcome::Draw, Outcome::Lose] {
+                assert_eq!(rules.outcome(rules.respond(result, own), own), result);
+            }
+        }
+        let (rock, gun) = (rules.find("Rock").unwrap(), rules.find("Gun").unwrap());
+        assert_eq!(rules.outcome(gun, rock), Outcome::Win);
+        let sponge = rules.find("Sponge").unwrap();
+        assert_eq!(rules.outcome(rock, sponge), Outcome::Win);
+    }
+
+    #[test]
+    fn invalid_rules_are_rejected() {
+        assert!(matches!(
+            Rules::parse("Rock 1 A X\nPaper 2 B Y\n"),
+            Err(AocError::Parse { line: 2, .. })
+        ));
+        assert!(matches!(
+            Rules::parse("Rock 1 A X\nPaper 2 A Y\nScissors 3 C Z\n"),
+            Err(AocError::Parse {
+                line: 2,
+                column: 9,
+                ..
+            })
+        ));
+        assert!(matches!(
+            Rules::parse("Rock one A X\n"),
+            Err(AocError::Parse { column: 6, .. })
+        ));
+        assert!(matches!(
+            read_strategy_guide("A Q\n".as_bytes(), Rules::classic()),
+            Err(AocError::Parse { column: 3, .. })
+        ));
+    }
+}
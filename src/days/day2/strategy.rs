@@ -0,0 +1,323 @@
+//! "What if the elf lied" experiments on a strategy guide: trying every decryption of X, Y and
+//! Z, and playing the guide against opponents that do not follow it.
+
+use super::{
+    Day2, Guide, GuideInterpretation, Outcome, Round, Rules, Score, Shape, CIPHER_LETTERS, OUTCOMES,
+};
+use crate::days::Solution;
+use crate::error::AocError;
+
+/// A map from the cipher letters X, Y and Z, in that order, to distinct shapes, as indices into
+/// the guide's rules, or to outcomes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Mapping {
+    Shapes([usize; 3]),
+    Outcomes([Outcome; 3]),
+}
+
+impl Mapping {
+    /// The mapping the elf actually meant for each interpretation, under [`Rules::classic`].
+    pub fn intended(interpretation: GuideInterpretation) -> Mapping {
+        match interpretation {
+            GuideInterpretation::AsShape => {
+                Mapping::Shapes([Shape::Rock, Shape::Paper, Shape::Scissors].map(Shape::index))
+            }
+            GuideInterpretation::AsOutcome => Mapping::Outcomes(OUTCOMES),
+        }
+    }
+
+    /// All mappings for `interpretation`: every choice of three distinct shapes of `rules`, or
+    /// the six orders of the outcomes.
+    pub fn all(rules: &Rules, interpretation: GuideInterpretation) -> Vec<Mapping> {
+        match interpretation {
+            GuideInterpretation::AsShape => {
+                let n = rules.len();
+                (0..n)
+                    .flat_map(|x| (0..n).flat_map(move |y| (0..n).map(move |z| [x, y, z])))
+                    .filter(|[x, y, z]| x != y && x != z && y != z)
+                    .map(Mapping::Shapes)
+                    .collect()
+            }
+            GuideInterpretation::AsOutcome => {
+                permutations(OUTCOMES).map(Mapping::Outcomes).to_vec()
+            }
+        }
+    }
+
+    /// The shape to play when the guide predicts `opponent` and says the cipher letter at index
+    /// `cipher` of [`CIPHER_LETTERS`].
+    pub fn own_shape(&self, rules: &Rules, opponent: usize, cipher: usize) -> usize {
+        match self {
+            Mapping::Shapes(shapes) => shapes[cipher],
+            Mapping::Outcomes(outcomes) => rules.respond(outcomes[cipher], opponent),
+        }
+    }
+}
+
+fn permutations<T: Copy>([a, b, c]: [T; 3]) -> [[T; 3]; 6] {
+    [
+        [a, b, c],
+        [a, c, b],
+        [b, a, c],
+        [b, c, a],
+        [c, a, b],
+        [c, b, a],
+    ]
+}
+
+/// The guide's rounds as opponent shape and cipher letter index.
+fn ciphered(guide: &Guide) -> Result<Vec<(usize, usize)>, AocError> {
+    guide
+        .rounds
+        .iter()
+        .enumerate()
+        .map(|(idx, (opponent, letter))| {
+            let cipher = CIPHER_LETTERS
+                .iter()
+                .position(|c| c == letter)
+                .ok_or_else(|| {
+                    AocError::no_solution(
+                        Day2::DAY,
+                        format!("round {}: '{}' is not X, Y or Z", idx + 1, letter),
+                    )
+                })?;
+            Ok((*opponent, cipher))
+        })
+        .collect()
+}
+
+fn score_ciphered(rules: &Rules, rounds: &[(usize, usize)], mapping: &Mapping) -> Score {
+    rounds.iter().fold(Score(0), |total, (opponent, cipher)| {
+        let own = mapping.own_shape(rules, *opponent, *cipher);
+        total + rules.round_score(own, *opponent)
+    })
+}
+
+pub fn score_with(guide: &Guide, mapping: &Mapping) -> Result<Score, AocError> {
+    Ok(score_ciphered(&guide.rules, &ciphered(guide)?, mapping))
+}
+
+/// The best and worst scoring decryptions of a guide; ties go to the earlier mapping.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Extremes {
+    pub best: (Mapping, Score),
+    pub worst: (Mapping, Score),
+}
+
+pub fn search(guide: &Guide, interpretation: GuideInterpretation) -> Result<Extremes, AocError> {
+    let rounds = ciphered(guide)?;
+    let scored = Mapping::all(&guide.rules, interpretation)
+        .into_iter()
+        .map(|mapping| (mapping, score_ciphered(&guide.rules, &rounds, &mapping)))
+        .collect::<Vec<(Mapping, Score)>>();
+    let mut extremes = Extremes {
+        best: scored[0],
+        worst: scored[0],
+    };
+    for candidate in &scored[1..] {
+        if candidate.1 > extremes.best.1 {
+            extremes.best = *candidate;
+        }
+        if candidate.1 < extremes.worst.1 {
+            extremes.worst = *candidate;
+        }
+    }
+    Ok(extremes)
+}
+
+/// A small deterministic generator (SplitMix64), so simulations can be replayed from a seed.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `0..n`; `n` must be positive.
+    pub fn below(&mut self, n: u64) -> u64 {
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+}
+
+/// How the simulated opponent picks its shape each round.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Opponent {
+    /// Every shape equally likely.
+    Random,
+    /// Shapes drawn with the frequencies of the guide's opponent column.
+    Frequency,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Simulation {
+    pub rounds: usize,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    pub total: u64,
+}
+
+impl Simulation {
+    pub fn mean(&self) -> Option<f64> {
+        (self.rounds > 0).then(|| self.total as f64 / self.rounds as f64)
+    }
+}
+
+/// Plays `rounds` rounds, cycling through the guide decrypted with `mapping`, against an
+/// opponent that ignores the guide's predictions.
+pub fn simulate(
+    guide: &Guide,
+    mapping: &Mapping,
+    opponent: Opponent,
+    rounds: usize,
+    seed: u64,
+) -> Result<Simulation, AocError> {
+    let mut result = Simulation::default();
+    let ciphered = ciphered(guide)?;
+    if ciphered.is_empty() {
+        return Ok(result);
+    }
+    let rules = &guide.rules;
+    let mut rng = Rng::new(seed);
+    let mut counts = vec![0u64; rules.len()];
+    for (predicted, _) in &ciphered {
+        counts[*predicted] += 1;
+    }
+
+    for (predicted, cipher) in ciphered.iter().cycle().take(rounds) {
+        let actual = match opponent {
+            Opponent::Random => rng.below(rules.len() as u64) as usize,
+            Opponent::Frequency => {
+                let mut pick = rng.below(ciphered.len() as u64);
+                counts
+                    .iter()
+                    .position(|count| {
+                        let hit = pick < *count;
+                        pick = pick.saturating_sub(*count);
+                        hit
+                    })
+                    .unwrap()
+            }
+        };
+        let round = Round::new(rules, actual, mapping.own_shape(rules, *predicted, *cipher));
+        match round.outcome {
+            Outcome::Win => result.wins += 1,
+            Outcome::Draw => result.draws += 1,
+            Outcome::Lose => result.losses += 1,
+        }
+        result.rounds += 1;
+        result.total += round.score.value() as u64;
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{classic_guide, EXAMPLE};
+    use super::super::{read_strategy_guide, RPS15_RULES, RPSLS_RULES};
+    use super::*;
+
+    #[test]
+    fn intended_mappings_match_parts() {
+        let guide = classic_guide(EXAMPLE);
+        let shape = Mapping::intended(GuideInterpretation::AsShape);
+        let outcome = Mapping::intended(GuideInterpretation::AsOutcome);
+        assert_eq!(score_with(&guide, &shape).unwrap(), Score(15));
+        assert_eq!(score_with(&guide, &outcome).unwrap(), Score(12));
+        let all = Mapping::all(&guide.rules, GuideInterpretation::AsShape);
+        assert_eq!(all.len(), 6);
+        assert!(all.contains(&shape));
+    }
+
+    #[test]
+    fn example_shape_search() {
+        let guide = classic_guide(EXAMPLE);
+        let extremes = search(&guide, GuideInterpretation::AsShape).unwrap();
+        assert_eq!(
+            extremes.best,
+            (
+                Mapping::Shapes([Shape::Scissors, Shape::Paper, Shape::Rock].map(Shape::index)),
+                Score(24)
+            )
+        );
+        assert_eq!(
+            extremes.worst,
+            (
+                Mapping::Shapes([Shape::Rock, Shape::Scissors, Shape::Paper].map(Shape::index)),
+                Score(6)
+            )
+        );
+    }
+
+    #[test]
+    fn example_outcome_search() {
+        let guide = classic_guide(EXAMPLE);
+        let extremes = search(&guide, GuideInterpretation::AsOutcome).unwrap();
+        assert_eq!(
+            extremes.best,
+            (
+                Mapping::Outcomes([Outcome::Win, Outcome::Lose, Outcome::Draw]),
+                Score(18)
+            )
+        );
+        assert_eq!(extremes.worst.1, Score(12));
+    }
+
+    #[test]
+    fn variant_search() {
+        let rules = Rules::parse(RPSLS_RULES).unwrap();
+        let guide = read_strategy_guide(EXAMPLE.as_bytes(), rules).unwrap();
+        assert_eq!(
+            Mapping::all(&guide.rules, GuideInterpretation::AsShape).len(),
+            60
+        );
+        // Paper beats Rock, Lizard beats Paper and Spock beats Scissors, all with distinct letters.
+        let extremes = search(&guide, GuideInterpretation::AsShape).unwrap();
+        assert_eq!(extremes.best.1, Score(2 + 4 + 5 + 3 * 6));
+
+        let rules = Rules::parse(RPS15_RULES).unwrap();
+        let guide = read_strategy_guide("A a\n".as_bytes(), rules).unwrap();
+        assert!(matches!(
+            search(&guide, GuideInterpretation::AsOutcome),
+            Err(AocError::NoSolution { .. })
+        ));
+    }
+
+    #[test]
+    fn frequency_opponent_follows_guide_column() {
+        let guide = classic_guide("A X\nA X\n");
+        let paper = Mapping::Shapes([Shape::Paper, Shape::Rock, Shape::Scissors].map(Shape::index));
+        let result = simulate(&guide, &paper, Opponent::Frequency, 100, 7).unwrap();
+        assert_eq!(result.wins, 100);
+        assert_eq!(result.total, 800);
+        assert_eq!(result.mean(), Some(8.0));
+    }
+
+    #[test]
+    fn random_opponent_is_balanced_and_replayable() {
+        let guide = classic_guide(EXAMPLE);
+        let mapping = Mapping::intended(GuideInterpretation::AsShape);
+        let result = simulate(&guide, &mapping, Opponent::Random, 3000, 42).unwrap();
+        assert_eq!(result.wins + result.draws + result.losses, 3000);
+        for count in [result.wins, result.draws, result.losses] {
+            assert!((850..1150).contains(&count), "{:?}", result);
+        }
+        assert_eq!(
+            simulate(&guide, &mapping, Opponent::Random, 3000, 42).unwrap(),
+            result
+        );
+        assert_eq!(
+            simulate(&classic_guide(""), &mapping, Opponent::Random, 10, 42).unwrap(),
+            Simulation::default()
+        );
+    }
+}
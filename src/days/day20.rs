@@ -0,0 +1,144 @@
+// mod day20
+
+use std::io::BufRead;
+
+use super::Solution;
+use crate::error::AocError;
+
+pub struct Day20;
+
+pub const DECRYPTION_KEY: i64 = 811_589_153;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+    type Input = Vec<i64>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Self::Input, AocError> {
+        read_file(reader)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Answer1, AocError> {
+        grove_coordinates(&mix(input, 1))
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Self::Answer2, AocError> {
+        let decrypted = input
+            .iter()
+            .map(|v| {
+                v.checked_mul(DECRYPTION_KEY).ok_or_else(|| {
+                    AocError::no_solution(Day20::DAY, format!("{} overflows once decrypted", v))
+                })
+            })
+            .collect::<Result<Vec<i64>, AocError>>()?;
+        grove_coordinates(&mix(&decrypted, 10))
+    }
+}
+
+pub fn print_answer() -> Result<(), AocError> {
+    super::print_solution(&Day20)
+}
+
+pub fn read_file<R>(reader: R) -> Result<Vec<i64>, AocError>
+where
+    R: BufRead,
+{
+    reader
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Ok(l) if l.trim().is_empty()))
+        .map(|(line_idx, line)| {
+            let line = line?;
+            line.trim().parse().map_err(|_| {
+                let column = line.len() - line.trim_start().len() + 1;
+                AocError::parse(Day20::DAY, line_idx + 1, column, &line, "Invalid number")
+            })
+        })
+        .collect()
+}
+
+/// Moves every number by its own value, in original order, `rounds` times.
+pub fn mix(numbers: &[i64], rounds: usize) -> Vec<i64> {
+    let mut order = (0..numbers.len()).collect::<Vec<usize>>();
+    let cycle = numbers.len() as i64 - 1;
+    if cycle <= 0 {
+        return numbers.to_vec();
+    }
+    for _ in 0..rounds {
+        for (original, value) in numbers.iter().enumerate() {
+            let pos = order.iter().position(|idx| *idx == original).unwrap();
+            order.remove(pos);
+            // Reducing first keeps the sum in range for values near i64::MAX.
+            let target = (pos as i64 + value.rem_euclid(cycle)).rem_euclid(cycle) as usize;
+            order.insert(target, original);
+        }
+    }
+    order.iter().map(|idx| numbers[*idx]).collect()
+}
+
+/// Sum of the 1000th, 2000th and 3000th numbers after the 0.
+pub fn grove_coordinates(mixed: &[i64]) -> Result<i64, AocError> {
+    let zero = mixed
+        .iter()
+        .position(|v| *v == 0)
+        .ok_or_else(|| AocError::no_solution(Day20::DAY, "file contains no 0"))?;
+    [1000, 2000, 3000]
+        .iter()
+        .map(|offset| mixed[(zero + offset) % mixed.len()])
+        .try_fold(0i64, |sum, v| sum.checked_add(v))
+        .ok_or_else(|| AocError::no_solution(Day20::DAY, "grove coordinates overflow i64"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "1\n2\n-3\n3\n-2\n0\n4\n";
+
+    fn rotate_to_zero(mixed: &[i64]) -> Vec<i64> {
+        let zero = mixed.iter().position(|v| *v == 0).unwrap();
+        mixed[zero..]
+            .iter()
+            .chain(&mixed[..zero])
+            .copied()
+            .collect()
+    }
+
+    #[test]
+    fn example_single_mix() {
+        let numbers = read_file(EXAMPLE.as_bytes()).unwrap();
+        let mixed = mix(&numbers, 1);
+        assert_eq!(rotate_to_zero(&mixed), [0, 3, -2, 1, 2, -3, 4]);
+        assert_eq!(grove_coordinates(&mixed).unwrap(), 3);
+    }
+
+    #[test]
+    fn example_decrypted() {
+        let numbers = read_file(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(Day20.part2(&numbers).unwrap(), 1623178306);
+    }
+
+    #[test]
+    fn extreme_values_do_not_overflow() {
+        let numbers = [i64::MAX, 0, i64::MIN, 1];
+        let mixed = mix(&numbers, 1);
+        assert_eq!(rotate_to_zero(&mixed), [0, i64::MAX, i64::MIN, 1]);
+        assert!(matches!(
+            grove_coordinates(&[0, i64::MAX, i64::MAX]),
+            Err(AocError::NoSolution { .. })
+        ));
+        assert!(matches!(
+            Day20.part2(&numbers.to_vec()),
+            Err(AocError::NoSolution { .. })
+        ));
+    }
+
+    #[test]
+    fn missing_zero_is_no_solution() {
+        assert!(matches!(
+            grove_coordinates(&[1, 2, 3]),
+            Err(AocError::NoSolution { .. })
+        ));
+    }
+}
@@ -0,0 +1,299 @@
+// mod day21
+
+use std::{collections::HashMap, io::BufRead};
+
+use super::Solution;
+use crate::error::AocError;
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+    type Input = HashMap<String, Job>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Self::Input, AocError> {
+        read_jobs(reader)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Answer1, AocError> {
+        evaluate(input, ROOT)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Self::Answer2, AocError> {
+        solve_for_human(input)
+    }
+}
+
+pub fn print_answer() -> Result<(), AocError> {
+    super::print_solution(&Day21)
+}
+
+pub const ROOT: &str = "root";
+pub const HUMAN: &str = "humn";
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Job {
+    Number(i64),
+    Math(String, Op, String),
+}
+
+pub fn read_jobs<R>(reader: R) -> Result<HashMap<String, Job>, AocError>
+where
+    R: BufRead,
+{
+    reader
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Ok(l) if l.trim().is_empty()))
+        .map(|(line_idx, line)| {
+            let line = line?;
+            let parse_err =
+                |column, reason| AocError::parse(Day21::DAY, line_idx + 1, column, &line, reason);
+            let (name, job) = line
+                .split_once(": ")
+                .ok_or_else(|| parse_err(1, "Expected '<name>: <job>'"))?;
+            let column = name.len() + 3;
+            let job = match job.split(' ').collect::<Vec<&str>>()[..] {
+                [number] => Job::Number(
+                    number
+                        .parse()
+                        .map_err(|_| parse_err(column, "Invalid number"))?,
+                ),
+                [a, op, b] => {
+                    let op = match op {
+                        "+" => Op::Add,
+                        "-" => Op::Sub,
+                        "*" => Op::Mul,
+                        "/" => Op::Div,
+                        _ => return Err(parse_err(column + a.len() + 1, "Unknown operator")),
+                    };
+                    Job::Math(a.to_owned(), op, b.to_owned())
+                }
+                _ => return Err(parse_err(column, "Expected a number or '<a> <op> <b>'")),
+            };
+            Ok((name.to_owned(), job))
+        })
+        .collect()
+}
+
+fn job<'a>(jobs: &'a HashMap<String, Job>, name: &str) -> Result<&'a Job, AocError> {
+    jobs.get(name)
+        .ok_or_else(|| AocError::no_solution(Day21::DAY, format!("unknown monkey {}", name)))
+}
+
+fn apply(op: Op, a: i64, b: i64) -> Result<i64, AocError> {
+    let (result, symbol) = match op {
+        Op::Add => (a.checked_add(b), '+'),
+        Op::Sub => (a.checked_sub(b), '-'),
+        Op::Mul => (a.checked_mul(b), '*'),
+        Op::Div if b == 0 => return Err(AocError::no_solution(Day21::DAY, "division by zero")),
+        Op::Div => (a.checked_div(b), '/'),
+    };
+    result.ok_or_else(|| {
+        AocError::no_solution(Day21::DAY, format!("{} {} {} overflows i64", a, symbol, b))
+    })
+}
+
+/// Fails if a monkey's job depends, directly or not, on its own number.
+fn check_acyclic<'a>(
+    jobs: &'a HashMap<String, Job>,
+    name: &'a str,
+    finished: &mut HashMap<&'a str, bool>,
+) -> Result<(), AocError> {
+    match finished.get(name) {
+        Some(true) => return Ok(()),
+        Some(false) => {
+            return Err(AocError::no_solution(
+                Day21::DAY,
+                format!("{} depends on its own number", name),
+            ))
+        }
+        None => {}
+    }
+    finished.insert(name, false);
+    if let Job::Math(a, _, b) = job(jobs, name)? {
+        check_acyclic(jobs, a, finished)?;
+        check_acyclic(jobs, b, finished)?;
+    }
+    finished.insert(name, true);
+    Ok(())
+}
+
+pub fn evaluate(jobs: &HashMap<String, Job>, name: &str) -> Result<i64, AocError> {
+    check_acyclic(jobs, name, &mut HashMap::new())?;
+    evaluate_acyclic(jobs, name)
+}
+
+fn evaluate_acyclic(jobs: &HashMap<String, Job>, name: &str) -> Result<i64, AocError> {
+    match job(jobs, name)? {
+        Job::Number(n) => Ok(*n),
+        Job::Math(a, op, b) => apply(*op, evaluate_acyclic(jobs, a)?, evaluate_acyclic(jobs, b)?),
+    }
+}
+
+fn depends_on_human(jobs: &HashMap<String, Job>, name: &str) -> Result<bool, AocError> {
+    if name == HUMAN {
+        return Ok(true);
+    }
+    match job(jobs, name)? {
+        Job::Number(_) => Ok(false),
+        Job::Math(a, _, b) => Ok(depends_on_human(jobs, a)? || depends_on_human(jobs, b)?),
+    }
+}
+
+/// The number `humn` must yell so that `name` evaluates to `target`.
+fn solve(jobs: &HashMap<String, Job>, name: &str, target: i64) -> Result<i64, AocError> {
+    if name == HUMAN {
+        return Ok(target);
+    }
+    let Job::Math(a, op, b) = job(jobs, name)? else {
+        return Err(AocError::no_solution(
+            Day21::DAY,
+            format!("{} does not depend on {}", name, HUMAN),
+        ));
+    };
+    if depends_on_human(jobs, a)? {
+        let known = evaluate_acyclic(jobs, b)?;
+        let target = match op {
+            Op::Add => apply(Op::Sub, target, known)?,
+            Op::Sub => apply(Op::Add, target, known)?,
+            Op::Mul => apply(Op::Div, target, known)?,
+            Op::Div => apply(Op::Mul, target, known)?,
+        };
+        solve(jobs, a, target)
+    } else {
+        let known = evaluate_acyclic(jobs, a)?;
+        let target = match op {
+            Op::Add => apply(Op::Sub, target, known)?,
+            Op::Sub => apply(Op::Sub, known, target)?,
+            Op::Mul => apply(Op::Div, target, known)?,
+            Op::Div => apply(Op::Div, known, target)?,
+        };
+        solve(jobs, b, target)
+    }
+}
+
+/// The number to yell so that both operands of `root` are equal.
+pub fn solve_for_human(jobs: &HashMap<String, Job>) -> Result<i64, AocError> {
+    check_acyclic(jobs, ROOT, &mut HashMap::new())?;
+    let Job::Math(a, _, b) = job(jobs, ROOT)? else {
+        return Err(AocError::no_solution(Day21::DAY, "root has no operands"));
+    };
+    let (unknown, known) = if depends_on_human(jobs, a)? {
+        (a, b)
+    } else {
+        (b, a)
+    };
+    let answer = solve(jobs, unknown, evaluate_acyclic(jobs, known)?)?;
+
+    let mut check = jobs.clone();
+    check.insert(HUMAN.to_owned(), Job::Number(answer));
+    if evaluate(&check, unknown)? != evaluate(&check, known)? {
+        return Err(AocError::no_solution(
+            Day21::DAY,
+            "no integer makes both sides of root equal",
+        ));
+    }
+    Ok(answer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "root: pppw + sjmn
+dbpl: 5
+cczh: sllz + lgvd
+zczc: 2
+ptdq: humn - dvpt
+dvpt: 3
+lfqf: 4
+humn: 5
+ljgn: 2
+sjmn: drzm * dbpl
+sllz: 4
+pppw: cczh / lfqf
+lgvd: ljgn * ptdq
+drzm: hmdt - zczc
+hmdt: 32
+";
+
+    #[test]
+    fn example_root() {
+        let jobs = read_jobs(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(
+            jobs["ptdq"],
+            Job::Math("humn".to_owned(), Op::Sub, "dvpt".to_owned())
+        );
+        assert_eq!(evaluate(&jobs, ROOT).unwrap(), 152);
+    }
+
+    #[test]
+    fn example_human() {
+        let jobs = read_jobs(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(solve_for_human(&jobs).unwrap(), 301);
+    }
+
+    #[test]
+    fn unknown_monkey_is_no_solution() {
+        let jobs = read_jobs("root: aaaa + bbbb\naaaa: 1\n".as_bytes()).unwrap();
+        assert!(matches!(
+            evaluate(&jobs, ROOT),
+            Err(AocError::NoSolution { .. })
+        ));
+    }
+
+    #[test]
+    fn cycles_and_overflow_are_no_solution() {
+        for input in [
+            "root: root + root\n",
+            "root: aaaa * humn\naaaa: bbbb - cccc\nbbbb: 1\ncccc: aaaa / bbbb\nhumn: 1\n",
+        ] {
+            let jobs = read_jobs(input.as_bytes()).unwrap();
+            assert!(matches!(
+                evaluate(&jobs, ROOT),
+                Err(AocError::NoSolution { .. })
+            ));
+            assert!(matches!(
+                solve_for_human(&jobs),
+                Err(AocError::NoSolution { .. })
+            ));
+        }
+
+        let input = format!("root: aaaa * bbbb\naaaa: {}\nbbbb: 2\n", i64::MAX);
+        let jobs = read_jobs(input.as_bytes()).unwrap();
+        assert!(matches!(
+            evaluate(&jobs, ROOT),
+            Err(AocError::NoSolution { .. })
+        ));
+
+        // bbbb equals aaaa only if humn is i64::MAX + 1.
+        let input = format!(
+            "root: aaaa + bbbb\naaaa: {}\nbbbb: humn - cccc\ncccc: 1\nhumn: 0\n",
+            i64::MAX
+        );
+        let jobs = read_jobs(input.as_bytes()).unwrap();
+        assert!(matches!(
+            solve_for_human(&jobs),
+            Err(AocError::NoSolution { .. })
+        ));
+    }
+
+    #[test]
+    fn invalid_operator_reports_column() {
+        assert!(matches!(
+            read_jobs("root: aaaa % bbbb\n".as_bytes()),
+            Err(AocError::Parse { column: 12, .. })
+        ));
+    }
+}
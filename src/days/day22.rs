@@ -0,0 +1,469 @@
+// mod day22
+
+use std::{
+    collections::{HashMap, VecDeque},
+    io::BufRead,
+};
+
+use super::Solution;
+use crate::error::AocError;
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u8 = 22;
+    type Input = (Board, Vec<Step>);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Self::Input, AocError> {
+        read_notes(reader)
+    }
+
+    fn part1(&self, (board, path): &Self::Input) -> Result<Self::Answer1, AocError> {
+        board.follow(path, &Wrap::Flat)
+    }
+
+    fn part2(&self, (board, path): &Self::Input) -> Result<Self::Answer2, AocError> {
+        board.follow(path, &Wrap::Cube(board.fold_cube()?))
+    }
+}
+
+pub fn print_answer() -> Result<(), AocError> {
+    super::print_solution(&Day22)
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Step {
+    Forward(usize),
+    Left,
+    Right,
+}
+
+/// Row and column deltas for facing right, down, left and up, the puzzle's facing values.
+const DIRECTIONS: [(isize, isize); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Position {
+    pub row: usize,
+    pub col: usize,
+    pub facing: usize,
+}
+
+impl Position {
+    pub fn password(&self) -> usize {
+        1000 * (self.row + 1) + 4 * (self.col + 1) + self.facing
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Board {
+    /// Map tiles: `b' '` off the board, `b'.'` open, `b'#'` wall.
+    rows: Vec<Vec<u8>>,
+}
+
+type Vec3 = [i32; 3];
+
+fn neg(v: Vec3) -> Vec3 {
+    [-v[0], -v[1], -v[2]]
+}
+
+fn dot(a: Vec3, b: Vec3) -> i32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// Orientation of one cube face: 3D directions of increasing column and row, and its outward
+/// normal.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct Frame {
+    right: Vec3,
+    down: Vec3,
+    normal: Vec3,
+}
+
+impl Frame {
+    /// Frame of the face glued to this one across the edge in `facing` direction of the net.
+    fn fold(&self, facing: usize) -> Frame {
+        let Frame {
+            right,
+            down,
+            normal,
+        } = *self;
+        match facing {
+            0 => Frame {
+                right: neg(normal),
+                down,
+                normal: right,
+            },
+            1 => Frame {
+                right,
+                down: neg(normal),
+                normal: down,
+            },
+            2 => Frame {
+                right: normal,
+                down,
+                normal: neg(right),
+            },
+            _ => Frame {
+                right,
+                down: normal,
+                normal: neg(down),
+            },
+        }
+    }
+
+    fn direction(&self, facing: usize) -> Vec3 {
+        [self.right, self.down, neg(self.right), neg(self.down)][facing]
+    }
+}
+
+/// The six faces of the folded cube, keyed by outward normal.
+#[derive(Clone, Debug)]
+pub struct Cube {
+    size: usize,
+    faces: HashMap<Vec3, ((usize, usize), Frame)>,
+    frames: HashMap<(usize, usize), Frame>,
+}
+
+pub enum Wrap {
+    /// Walking off an edge re-enters on the opposite side of the same row or column.
+    Flat,
+    Cube(Cube),
+}
+
+impl Board {
+    fn tile(&self, row: isize, col: isize) -> u8 {
+        if row < 0 || col < 0 {
+            return b' ';
+        }
+        self.rows
+            .get(row as usize)
+            .and_then(|r| r.get(col as usize))
+            .copied()
+            .unwrap_or(b' ')
+    }
+
+    fn start(&self) -> Option<Position> {
+        let col = self.rows.first()?.iter().position(|t| *t == b'.')?;
+        Some(Position {
+            row: 0,
+            col,
+            facing: 0,
+        })
+    }
+
+    pub fn fold_cube(&self) -> Result<Cube, AocError> {
+        let tiles = self.rows.iter().flatten().filter(|t| **t != b' ').count();
+        let size = (1..=tiles).find(|n| 6 * n * n >= tiles).unwrap_or(0);
+        if size == 0 || 6 * size * size != tiles {
+            return Err(AocError::no_solution(
+                Day22::DAY,
+                "board does not fold into a cube",
+            ));
+        }
+
+        let on_board = |(fr, fc): (usize, usize)| {
+            self.tile((fr * size) as isize, (fc * size) as isize) != b' '
+        };
+        let first = (0..)
+            .map(|fc| (0, fc))
+            .find(|face| on_board(*face))
+            .unwrap();
+        let mut frames = HashMap::from([(
+            first,
+            Frame {
+                right: [1, 0, 0],
+                down: [0, 1, 0],
+                normal: [0, 0, -1],
+            },
+        )]);
+        let mut queue = VecDeque::from([first]);
+        while let Some(face) = queue.pop_front() {
+            let frame = frames[&face];
+            for (facing, (dr, dc)) in DIRECTIONS.iter().enumerate() {
+                let (Some(fr), Some(fc)) = (
+                    face.0.checked_add_signed(*dr),
+                    face.1.checked_add_signed(*dc),
+                ) else {
+                    continue;
+                };
+                if on_board((fr, fc)) && !frames.contains_key(&(fr, fc)) {
+                    frames.insert((fr, fc), frame.fold(facing));
+                    queue.push_back((fr, fc));
+                }
+            }
+        }
+
+        let faces = frames
+            .iter()
+            .map(|(face, frame)| (frame.normal, (*face, *frame)))
+            .collect::<HashMap<Vec3, ((usize, usize), Frame)>>();
+        if frames.len() != 6 || faces.len() != 6 {
+            return Err(AocError::no_solution(
+                Day22::DAY,
+                "board does not fold into a cube",
+            ));
+        }
+        Ok(Cube {
+            size,
+            faces,
+            frames,
+        })
+    }
+
+    fn wrap(&self, pos: Position, wrap: &Wrap) -> Position {
+        let (dr, dc) = DIRECTIONS[pos.facing];
+        match wrap {
+            Wrap::Flat => {
+                let (mut row, mut col) = (pos.row as isize, pos.col as isize);
+                while self.tile(row - dr, col - dc) != b' ' {
+                    row -= dr;
+                    col -= dc;
+                }
+                Position {
+                    row: row as usize,
+                    col: col as usize,
+                    facing: pos.facing,
+                }
+            }
+            Wrap::Cube(cube) => {
+                let n = cube.size as i32;
+                let face = (pos.row / cube.size, pos.col / cube.size);
+                let frame = cube.frames[&face];
+                let (r, c) = ((pos.row % cube.size) as i32, (pos.col % cube.size) as i32);
+                // Cell centres in doubled coordinates on a cube spanning -n..=n.
+                let p: Vec3 = std::array::from_fn(|i| {
+                    frame.normal[i] * n
+                        + frame.right[i] * (2 * c + 1 - n)
+                        + frame.down[i] * (2 * r + 1 - n)
+                });
+                let travel = frame.direction(pos.facing);
+                let q: Vec3 = std::array::from_fn(|i| p[i] + travel[i] - frame.normal[i]);
+
+                let ((fr, fc), target) = cube.faces[&travel];
+                let entering = neg(frame.normal);
+                let facing = (0..4).find(|f| target.direction(*f) == entering).unwrap();
+                Position {
+                    row: fr * cube.size + ((dot(q, target.down) + n - 1) / 2) as usize,
+                    col: fc * cube.size + ((dot(q, target.right) + n - 1) / 2) as usize,
+                    facing,
+                }
+            }
+        }
+    }
+
+    fn step(&self, pos: Position, wrap: &Wrap) -> Position {
+        let (dr, dc) = DIRECTIONS[pos.facing];
+        let (row, col) = (pos.row as isize + dr, pos.col as isize + dc);
+        if self.tile(row, col) == b' ' {
+            self.wrap(pos, wrap)
+        } else {
+            Position {
+                row: row as usize,
+                col: col as usize,
+                facing: pos.facing,
+            }
+        }
+    }
+
+    pub fn walk(&self, path: &[Step], wrap: &Wrap) -> Result<Position, AocError> {
+        let mut pos = self
+            .start()
+            .ok_or_else(|| AocError::no_solution(Day22::DAY, "no open tile on the top row"))?;
+        for step in path {
+            match step {
+                Step::Left => pos.facing = (pos.facing + 3) % 4,
+                Step::Right => pos.facing = (pos.facing + 1) % 4,
+                Step::Forward(n) => {
+                    for _ in 0..*n {
+                        let next = self.step(pos, wrap);
+                        if self.rows[next.row][next.col] == b'#' {
+                            break;
+                        }
+                        pos = next;
+                    }
+                }
+            }
+        }
+        Ok(pos)
+    }
+
+    pub fn follow(&self, path: &[Step], wrap: &Wrap) -> Result<usize, AocError> {
+        self.walk(path, wrap).map(|pos| pos.password())
+    }
+}
+
+fn parse_path(line: &str) -> Result<Vec<Step>, (usize, &'static str)> {
+    let mut steps = Vec::new();
+    // Where the number being read starts, and its value so far.
+    let mut number = None;
+    for (idx, c) in line.char_indices() {
+        match c {
+            '0'..='9' => {
+                let digit = c.to_digit(10).unwrap() as usize;
+                let (start, n): (usize, usize) = number.unwrap_or((idx, 0));
+                let n = n
+                    .checked_mul(10)
+                    .and_then(|n| n.checked_add(digit))
+                    .ok_or((start, "Step count too large"))?;
+                number = Some((start, n));
+                continue;
+            }
+            'L' | 'R' => {
+                if let Some((_, n)) = number.take() {
+                    steps.push(Step::Forward(n));
+                }
+                steps.push(if c == 'L' { Step::Left } else { Step::Right });
+            }
+            _ => return Err((idx, "Expected a number, 'L' or 'R'")),
+        }
+    }
+    if let Some((_, n)) = number {
+        steps.push(Step::Forward(n));
+    }
+    Ok(steps)
+}
+
+pub fn read_notes<R>(reader: R) -> Result<(Board, Vec<Step>), AocError>
+where
+    R: BufRead,
+{
+    let mut rows = Vec::new();
+    let mut lines = reader.lines().enumerate();
+    for (line_idx, line) in lines.by_ref() {
+        let line = line?;
+        if line.trim().is_empty() {
+            break;
+        }
+        if let Some(col) = line.find(|c| !matches!(c, ' ' | '.' | '#')) {
+            return Err(AocError::parse(
+                Day22::DAY,
+                line_idx + 1,
+                col + 1,
+                &line,
+                "Expected ' ', '.' or '#'",
+            ));
+        }
+        rows.push(line.into_bytes());
+    }
+
+    for (line_idx, line) in lines {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let path = parse_path(line.trim_end()).map_err(|(offset, reason)| {
+            AocError::parse(Day22::DAY, line_idx + 1, offset + 1, &line, reason)
+        })?;
+        return Ok((Board { rows }, path));
+    }
+    Err(AocError::no_solution(Day22::DAY, "notes contain no path"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "        ...#
+        .#..
+        #...
+        ....
+...#.......#
+........#...
+..#....#....
+..........#.
+        ...#....
+        .....#..
+        .#......
+        ......#.
+
+10R5L5R10L4R5L5
+";
+
+    #[test]
+    fn example_parse() {
+        let (board, path) = read_notes(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(board.rows.len(), 12);
+        assert_eq!(path.len(), 13);
+        assert_eq!(
+            path[..3],
+            [Step::Forward(10), Step::Right, Step::Forward(5)]
+        );
+    }
+
+    #[test]
+    fn example_flat_walk() {
+        let (board, path) = read_notes(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(
+            board.walk(&path, &Wrap::Flat).unwrap(),
+            Position {
+                row: 5,
+                col: 7,
+                facing: 0
+            }
+        );
+        assert_eq!(board.follow(&path, &Wrap::Flat).unwrap(), 6032);
+    }
+
+    #[test]
+    fn example_cube_walk() {
+        let (board, path) = read_notes(EXAMPLE.as_bytes()).unwrap();
+        let cube = Wrap::Cube(board.fold_cube().unwrap());
+        assert_eq!(
+            board.walk(&path, &cube).unwrap(),
+            Position {
+                row: 4,
+                col: 6,
+                facing: 3
+            }
+        );
+        assert_eq!(board.follow(&path, &cube).unwrap(), 5031);
+    }
+
+    #[test]
+    fn cube_wrap_is_reversible() {
+        let (board, _) = read_notes(EXAMPLE.as_bytes()).unwrap();
+        let cube = Wrap::Cube(board.fold_cube().unwrap());
+        for (row, line) in board.rows.iter().enumerate() {
+            for (col, tile) in line.iter().enumerate() {
+                if *tile == b' ' {
+                    continue;
+                }
+                for facing in 0..4 {
+                    let there = board.step(Position { row, col, facing }, &cube);
+                    let back = board.step(
+                        Position {
+                            facing: (there.facing + 2) % 4,
+                            ..there
+                        },
+                        &cube,
+                    );
+                    assert_eq!((back.row, back.col), (row, col));
+                    assert_eq!(back.facing, (facing + 2) % 4);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_path_reports_column() {
+        assert!(matches!(
+            read_notes("..\n..\n\n1R2X\n".as_bytes()),
+            Err(AocError::Parse {
+                line: 4,
+                column: 4,
+                ..
+            })
+        ));
+        let input = format!("..\n..\n\n1R{}0\n", usize::MAX);
+        assert!(matches!(
+            read_notes(input.as_bytes()),
+            Err(AocError::Parse {
+                line: 4,
+                column: 3,
+                ..
+            })
+        ));
+    }
+}
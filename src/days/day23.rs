@@ -0,0 +1,206 @@
+// mod day23
+
+use std::{
+    collections::{HashMap, HashSet},
+    io::BufRead,
+};
+
+use super::Solution;
+use crate::error::AocError;
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+    type Input = HashSet<Elf>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Self::Input, AocError> {
+        read_grove(reader)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Answer1, AocError> {
+        let mut grove = Grove::new(input.clone());
+        for _ in 0..10 {
+            grove.round();
+        }
+        Ok(grove.empty_ground())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Self::Answer2, AocError> {
+        let mut grove = Grove::new(input.clone());
+        let mut rounds = 1;
+        while grove.round() {
+            rounds += 1;
+        }
+        Ok(rounds)
+    }
+}
+
+pub fn print_answer() -> Result<(), AocError> {
+    super::print_solution(&Day23)
+}
+
+/// Elf position as (row, column).
+pub type Elf = (i32, i32);
+
+type Offset = (i32, i32);
+
+/// Direction to propose in, with the three cells that must be free: north, south, west, east.
+const PROPOSALS: [(Offset, [Offset; 3]); 4] = [
+    ((-1, 0), [(-1, -1), (-1, 0), (-1, 1)]),
+    ((1, 0), [(1, -1), (1, 0), (1, 1)]),
+    ((0, -1), [(-1, -1), (0, -1), (1, -1)]),
+    ((0, 1), [(-1, 1), (0, 1), (1, 1)]),
+];
+
+pub struct Grove {
+    elves: HashSet<Elf>,
+    first_proposal: usize,
+}
+
+impl Grove {
+    pub fn new(elves: HashSet<Elf>) -> Self {
+        Grove {
+            elves,
+            first_proposal: 0,
+        }
+    }
+
+    pub fn elves(&self) -> &HashSet<Elf> {
+        &self.elves
+    }
+
+    fn proposal(&self, (row, col): Elf) -> Option<Elf> {
+        let occupied = |(dr, dc): Offset| self.elves.contains(&(row + dr, col + dc));
+        let alone = (-1..=1)
+            .flat_map(|dr| (-1..=1).map(move |dc| (dr, dc)))
+            .filter(|d| *d != (0, 0))
+            .all(|d| !occupied(d));
+        if alone {
+            return None;
+        }
+        (0..4)
+            .map(|i| PROPOSALS[(self.first_proposal + i) % 4])
+            .find(|(_, check)| check.iter().all(|d| !occupied(*d)))
+            .map(|((dr, dc), _)| (row + dr, col + dc))
+    }
+
+    /// Runs one round, returning whether any elf moved.
+    pub fn round(&mut self) -> bool {
+        let mut proposals: HashMap<Elf, Vec<Elf>> = HashMap::new();
+        for elf in &self.elves {
+            if let Some(target) = self.proposal(*elf) {
+                proposals.entry(target).or_default().push(*elf);
+            }
+        }
+        let mut moved = false;
+        for (target, from) in proposals {
+            if let [elf] = from[..] {
+                self.elves.remove(&elf);
+                self.elves.insert(target);
+                moved = true;
+            }
+        }
+        self.first_proposal = (self.first_proposal + 1) % 4;
+        moved
+    }
+
+    /// Empty tiles in the smallest rectangle containing every elf.
+    pub fn empty_ground(&self) -> usize {
+        let rows = self.elves.iter().map(|e| e.0);
+        let cols = self.elves.iter().map(|e| e.1);
+        let (Some(top), Some(bottom), Some(left), Some(right)) = (
+            rows.clone().min(),
+            rows.max(),
+            cols.clone().min(),
+            cols.max(),
+        ) else {
+            return 0;
+        };
+        ((bottom - top + 1) * (right - left + 1)) as usize - self.elves.len()
+    }
+}
+
+pub fn read_grove<R>(reader: R) -> Result<HashSet<Elf>, AocError>
+where
+    R: BufRead,
+{
+    let mut elves = HashSet::new();
+    for (line_idx, line) in reader.lines().enumerate() {
+        let line = line?;
+        for (col, c) in line.trim_end().chars().enumerate() {
+            match c {
+                '#' => {
+                    elves.insert((line_idx as i32, col as i32));
+                }
+                '.' => {}
+                _ => {
+                    return Err(AocError::parse(
+                        Day23::DAY,
+                        line_idx + 1,
+                        col + 1,
+                        &line,
+                        "Expected '.' or '#'",
+                    ))
+                }
+            }
+        }
+    }
+    Ok(elves)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "....#..
+..###.#
+#...#.#
+.#...##
+#.###..
+##.#.##
+.#..#..
+";
+
+    #[test]
+    fn small_example() {
+        let mut grove =
+            Grove::new(read_grove(".....\n..##.\n..#..\n.....\n..##.\n".as_bytes()).unwrap());
+        let mut rounds = 0;
+        while grove.round() {
+            rounds += 1;
+        }
+        assert_eq!(rounds, 3);
+        assert_eq!(
+            *grove.elves(),
+            HashSet::from([(0, 2), (1, 4), (2, 0), (3, 4), (5, 2)])
+        );
+    }
+
+    #[test]
+    fn example_empty_ground() {
+        let elves = read_grove(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(elves.len(), 22);
+        assert_eq!(Day23.part1(&elves).unwrap(), 110);
+    }
+
+    #[test]
+    fn example_settled_round() {
+        let elves = read_grove(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(Day23.part2(&elves).unwrap(), 20);
+    }
+
+    #[test]
+    fn invalid_tile_reports_column() {
+        assert!(matches!(
+            read_grove("..#\n.x.\n".as_bytes()),
+            Err(AocError::Parse {
+                line: 2,
+                column: 2,
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,252 @@
+// mod day24
+
+use std::{collections::HashSet, io::BufRead};
+
+use super::Solution;
+use crate::error::AocError;
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u8 = 24;
+    type Input = Valley;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Self::Input, AocError> {
+        read_valley(reader)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Answer1, AocError> {
+        input.crossing(input.entrance(), input.exit(), 0)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Self::Answer2, AocError> {
+        let (entrance, exit) = (input.entrance(), input.exit());
+        let there = input.crossing(entrance, exit, 0)?;
+        let back = input.crossing(exit, entrance, there)?;
+        input.crossing(entrance, exit, back)
+    }
+}
+
+pub fn print_answer() -> Result<(), AocError> {
+    super::print_solution(&Day24)
+}
+
+/// Position inside the valley walls as (row, column); the entrance is row -1.
+pub type Position = (isize, isize);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Valley {
+    /// Tiles inside the walls at minute 0, `b'.'` or one of `b"<>^v"`.
+    tiles: Vec<Vec<u8>>,
+    entrance_col: isize,
+    exit_col: isize,
+}
+
+impl Valley {
+    fn height(&self) -> isize {
+        self.tiles.len() as isize
+    }
+
+    fn width(&self) -> isize {
+        self.tiles[0].len() as isize
+    }
+
+    pub fn entrance(&self) -> Position {
+        (-1, self.entrance_col)
+    }
+
+    pub fn exit(&self) -> Position {
+        (self.height(), self.exit_col)
+    }
+
+    fn tile(&self, row: isize, col: isize) -> u8 {
+        self.tiles[row.rem_euclid(self.height()) as usize][col.rem_euclid(self.width()) as usize]
+    }
+
+    /// Whether `pos` is inside the valley and free of blizzards at `minute`.
+    pub fn is_free(&self, (row, col): Position, minute: usize) -> bool {
+        if (row, col) == self.entrance() || (row, col) == self.exit() {
+            return true;
+        }
+        if !(0..self.height()).contains(&row) || !(0..self.width()).contains(&col) {
+            return false;
+        }
+        let t = minute as isize;
+        self.tile(row, col - t) != b'>'
+            && self.tile(row, col + t) != b'<'
+            && self.tile(row - t, col) != b'v'
+            && self.tile(row + t, col) != b'^'
+    }
+
+    /// Minute of arrival at `to` when leaving `from` at minute `start`.
+    pub fn crossing(&self, from: Position, to: Position, start: usize) -> Result<usize, AocError> {
+        // Blizzards repeat with this period, so a longer search would only revisit states.
+        let period = (self.height() * self.width()) as usize;
+        let mut reachable = HashSet::from([from]);
+        for minute in start + 1..=start + period + 1 {
+            reachable = reachable
+                .iter()
+                .flat_map(|(row, col)| {
+                    [(0, 0), (-1, 0), (1, 0), (0, -1), (0, 1)].map(|(dr, dc)| (row + dr, col + dc))
+                })
+                .filter(|pos| self.is_free(*pos, minute))
+                .collect();
+            if reachable.contains(&to) {
+                return Ok(minute);
+            }
+            if reachable.is_empty() {
+                break;
+            }
+        }
+        Err(AocError::no_solution(
+            Day24::DAY,
+            "no path through the blizzards",
+        ))
+    }
+}
+
+pub fn read_valley<R>(reader: R) -> Result<Valley, AocError>
+where
+    R: BufRead,
+{
+    let mut rows = Vec::new();
+    for (line_idx, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(col) = line.find(|c| !matches!(c, '#' | '.' | '<' | '>' | '^' | 'v')) {
+            return Err(AocError::parse(
+                Day24::DAY,
+                line_idx + 1,
+                col + 1,
+                &line,
+                "Expected '#', '.' or a blizzard",
+            ));
+        }
+        if let Some(width) = rows.first().map(|(_, first): &(usize, String)| first.len()) {
+            if line.len() != width {
+                return Err(AocError::parse(
+                    Day24::DAY,
+                    line_idx + 1,
+                    line.len().min(width) + 1,
+                    &line,
+                    "Valley is not rectangular",
+                ));
+            }
+        }
+        rows.push((line_idx, line));
+    }
+
+    let gap = |(line_idx, line): &(usize, String)| {
+        let walls = line.bytes().filter(|b| *b == b'#').count();
+        match line.find('.') {
+            Some(col) if walls + 1 == line.len() && col > 0 && col + 1 < line.len() => {
+                Ok(col as isize - 1)
+            }
+            _ => Err(AocError::parse(
+                Day24::DAY,
+                line_idx + 1,
+                1,
+                line,
+                "Expected a wall with a single gap",
+            )),
+        }
+    };
+    let (Some(top), Some(bottom)) = (rows.first(), rows.last()) else {
+        return Err(AocError::no_solution(Day24::DAY, "empty valley"));
+    };
+    let (entrance_col, exit_col) = (gap(top)?, gap(bottom)?);
+
+    let inner = &rows[1..rows.len() - 1];
+    let mut tiles = Vec::with_capacity(inner.len());
+    for (line_idx, line) in inner {
+        let bytes = line.as_bytes();
+        if let Some(col) = (0..bytes.len()).find(|col| {
+            let is_edge = *col == 0 || *col + 1 == bytes.len();
+            (bytes[*col] == b'#') != is_edge
+        }) {
+            return Err(AocError::parse(
+                Day24::DAY,
+                line_idx + 1,
+                col + 1,
+                line,
+                "Walls must only surround the valley",
+            ));
+        }
+        tiles.push(bytes[1..bytes.len() - 1].to_vec());
+    }
+    if tiles.is_empty() || tiles[0].is_empty() {
+        return Err(AocError::no_solution(Day24::DAY, "valley has no interior"));
+    }
+    Ok(Valley {
+        tiles,
+        entrance_col,
+        exit_col,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "#.######
+#>>.<^<#
+#.<..<<#
+#>v.><>#
+#<^v^^>#
+######.#
+";
+
+    #[test]
+    fn simple_blizzards_move_and_wrap() {
+        let valley = read_valley(
+            "#.#####\n#.....#\n#>....#\n#.....#\n#...v.#\n#.....#\n#####.#\n".as_bytes(),
+        )
+        .unwrap();
+        assert!(!valley.is_free((1, 0), 0));
+        assert!(!valley.is_free((1, 4), 4));
+        assert!(valley.is_free((1, 0), 1));
+        assert!(!valley.is_free((1, 0), 5));
+        assert!(!valley.is_free((4, 3), 1));
+        assert!(!valley.is_free((0, 3), 2));
+    }
+
+    #[test]
+    fn example_crossing() {
+        let valley = read_valley(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(valley.entrance(), (-1, 0));
+        assert_eq!(valley.exit(), (4, 5));
+        assert_eq!(Day24.part1(&valley).unwrap(), 18);
+    }
+
+    #[test]
+    fn example_return_for_snacks() {
+        let valley = read_valley(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(
+            valley
+                .crossing(valley.exit(), valley.entrance(), 18)
+                .unwrap(),
+            41
+        );
+        assert_eq!(Day24.part2(&valley).unwrap(), 54);
+    }
+
+    #[test]
+    fn malformed_valley_is_error() {
+        assert!(matches!(
+            read_valley("#.###\n#.x.#\n###.#\n".as_bytes()),
+            Err(AocError::Parse {
+                line: 2,
+                column: 3,
+                ..
+            })
+        ));
+        assert!(matches!(
+            read_valley("#####\n#...#\n###.#\n".as_bytes()),
+            Err(AocError::Parse { line: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,160 @@
+// mod day25
+
+use std::{fmt, io::BufRead, str::FromStr};
+
+use super::Solution;
+use crate::error::AocError;
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+    type Input = Vec<Snafu>;
+    type Answer1 = Snafu;
+    type Answer2 = &'static str;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Self::Input, AocError> {
+        read_requirements(reader)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Answer1, AocError> {
+        input
+            .iter()
+            .try_fold(0i64, |sum, n| sum.checked_add(n.0))
+            .map(Snafu)
+            .ok_or_else(|| AocError::no_solution(Day25::DAY, "fuel requirement overflows"))
+    }
+
+    /// The last star is awarded for finishing every other puzzle; there is nothing to compute.
+    fn part2(&self, _input: &Self::Input) -> Result<Self::Answer2, AocError> {
+        Ok("Start the blender")
+    }
+}
+
+pub fn print_answer() -> Result<(), AocError> {
+    super::print_solution(&Day25)
+}
+
+/// A number written in balanced base five with digits `=`, `-`, `0`, `1` and `2`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Snafu(pub i64);
+
+impl FromStr for Snafu {
+    /// Byte offset of the invalid digit and the reason.
+    type Err = (usize, &'static str);
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err((0, "Empty number"));
+        }
+        s.char_indices()
+            .try_fold(0i64, |value, (idx, c)| {
+                let digit = match c {
+                    '=' => -2,
+                    '-' => -1,
+                    '0' => 0,
+                    '1' => 1,
+                    '2' => 2,
+                    _ => return Err((idx, "Expected one of '=', '-', '0', '1' or '2'")),
+                };
+                value
+                    .checked_mul(5)
+                    .and_then(|v| v.checked_add(digit))
+                    .ok_or((idx, "Number too large"))
+            })
+            .map(Snafu)
+    }
+}
+
+impl fmt::Display for Snafu {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut digits = Vec::new();
+        let mut n = self.0 as i128;
+        loop {
+            let digit = (n + 2).rem_euclid(5) - 2;
+            digits.push(b"=-012"[(digit + 2) as usize] as char);
+            n = (n - digit) / 5;
+            if n == 0 {
+                break;
+            }
+        }
+        write!(f, "{}", digits.iter().rev().collect::<String>())
+    }
+}
+
+pub fn read_requirements<R>(reader: R) -> Result<Vec<Snafu>, AocError>
+where
+    R: BufRead,
+{
+    reader
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Ok(l) if l.trim().is_empty()))
+        .map(|(line_idx, line)| {
+            let line = line?;
+            line.trim_end().parse().map_err(|(offset, reason)| {
+                AocError::parse(Day25::DAY, line_idx + 1, offset + 1, &line, reason)
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "1=-0-2
+12111
+2=0=
+21
+2=01
+111
+20012
+112
+1=-1=
+1-12
+12
+1=
+122
+";
+
+    #[test]
+    fn conversions() {
+        for (decimal, snafu) in [
+            (1, "1"),
+            (3, "1="),
+            (8, "2="),
+            (10, "20"),
+            (15, "1=0"),
+            (20, "1-0"),
+            (2022, "1=11-2"),
+            (12345, "1-0---0"),
+            (314159265, "1121-1110-1=0"),
+            (-1, "-"),
+            (0, "0"),
+        ] {
+            assert_eq!(snafu.parse::<Snafu>(), Ok(Snafu(decimal)));
+            assert_eq!(Snafu(decimal).to_string(), snafu);
+        }
+    }
+
+    #[test]
+    fn example_sum() {
+        let numbers = read_requirements(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(numbers[0], Snafu(1747));
+        assert_eq!(Day25.part1(&numbers).unwrap(), Snafu(4890));
+        assert_eq!(Day25.part1(&numbers).unwrap().to_string(), "2=-1=0");
+    }
+
+    #[test]
+    fn invalid_digit_reports_column() {
+        assert!(matches!(
+            read_requirements("1=\n12-3\n".as_bytes()),
+            Err(AocError::Parse {
+                line: 2,
+                column: 4,
+                ..
+            })
+        ));
+    }
+}
@@ -1,63 +1,397 @@
 // mod day3
 
-use std::fs::File;
+use std::fmt::{self, Display};
 use std::io::BufRead;
-use std::io::BufReader;
-use std::iter::Sum;
-use std::ops::Add;
-
-pub fn print_answer() {
-    println!(
-        "{:?}",
-        rucksack_priorities(BufReader::new(File::open("data/input_day3").unwrap()))
-    );
-    println!(
-        "{:?}",
-        rucksack_group_priorities(BufReader::new(File::open("data/input_day3").unwrap()))
-    );
-}
-
-struct Rucksack {
+use std::iter::{FromIterator, Sum};
+use std::ops::{Add, BitAnd, BitOr};
+
+use super::Solution;
+use crate::error::AocError;
+
+pub struct Day3;
+
+/// Part 2 looks for the badge of each group of this many elves.
+pub const GROUP_SIZE: usize = 3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    type Input = Vec<Rucksack>;
+    type Answer1 = Priority;
+    type Answer2 = Priority;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Self::Input, AocError> {
+        read_rucksacks(reader)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Answer1, AocError> {
+        rucksack_priorities(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Self::Answer2, AocError> {
+        rucksack_group_priorities(input)
+    }
+}
+
+pub fn print_answer() -> Result<(), AocError> {
+    super::print_solution(&Day3)
+}
+
+pub struct Rucksack {
+    line: usize,
     content: String,
 }
 
 impl Rucksack {
+    pub fn new(line: usize, content: String) -> Self {
+        Rucksack { line, content }
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
     pub fn contents(&self) -> &[u8] {
         self.content.trim().as_bytes()
     }
 
+    /// Both halves of the contents; with an odd number of items the second half has the extra.
     pub fn compartments(&self) -> (&[u8], &[u8]) {
         let content_slice = self.contents();
-        let compartment_size = content_slice.len();
-        (
-            content_slice.chunks(compartment_size / 2).next().unwrap(),
-            content_slice.rchunks(compartment_size / 2).next().unwrap(),
-        )
+        content_slice.split_at(content_slice.len() / 2)
+    }
+
+    /// Everything that prevents splitting the contents into two compartments of letters.
+    pub fn problems(&self) -> Vec<LintIssue> {
+        let offset = self.content.chars().count() - self.content.trim_start().chars().count();
+        let items = self.content.trim();
+        let mut problems = items
+            .chars()
+            .enumerate()
+            .filter(|(_, item)| Priority::try_from(*item).is_err())
+            .map(|(idx, item)| LintIssue::InvalidItem {
+                column: offset + idx + 1,
+                item,
+            })
+            .collect::<Vec<LintIssue>>();
+        match items.chars().count() {
+            0 => problems.insert(0, LintIssue::Empty),
+            n if n % 2 == 1 => problems.insert(0, LintIssue::OddLength(n)),
+            _ => {}
+        }
+        problems
     }
 
-    pub fn find_shared_item(&self) -> u8 {
+    pub fn items(&self) -> ItemSet {
+        ItemSet::from_items(self.contents())
+    }
+
+    /// Every item found in both compartments.
+    pub fn shared_items(&self) -> ItemSet {
         let (c1, c2) = self.compartments();
-        *c1.iter().find(|c| c2.contains(c)).unwrap()
+        ItemSet::from_items(c1) & ItemSet::from_items(c2)
     }
 
-    pub fn find_shared_by_group(&self, rucksack_1: &Rucksack, rucksack_2: &Rucksack) -> u8 {
-        *self
-            .contents()
+    /// Every item carried by this rucksack and all of `others`.
+    pub fn shared_with(&self, others: &[Rucksack]) -> ItemSet {
+        others
             .iter()
-            .find(|c| rucksack_1.contents().contains(c) && rucksack_2.contents().contains(c))
-            .unwrap()
+            .fold(self.items(), |shared, other| shared & other.items())
+    }
+}
+
+/// What a set of rucksacks has in common, when exactly one item is expected.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SharedItem {
+    None,
+    One(Priority),
+    Several(ItemSet),
+}
+
+impl SharedItem {
+    pub fn priority(&self) -> Option<Priority> {
+        match self {
+            SharedItem::One(priority) => Some(*priority),
+            _ => None,
+        }
+    }
+
+    /// The single item, or an error describing `what` was expected to share it.
+    fn single(self, what: impl Fn() -> String) -> Result<Priority, AocError> {
+        match self {
+            SharedItem::One(priority) => Ok(priority),
+            SharedItem::None => Err(AocError::no_solution(
+                Day3::DAY,
+                format!("no item shared {}", what()),
+            )),
+            SharedItem::Several(items) => Err(AocError::no_solution(
+                Day3::DAY,
+                format!(
+                    "items {} are all shared {}",
+                    items.iter().filter_map(|p| p.item()).collect::<String>(),
+                    what()
+                ),
+            )),
+        }
+    }
+}
+
+impl From<ItemSet> for SharedItem {
+    fn from(items: ItemSet) -> Self {
+        let mut iter = items.iter();
+        match (iter.next(), iter.next()) {
+            (None, _) => SharedItem::None,
+            (Some(item), None) => SharedItem::One(item),
+            (Some(_), Some(_)) => SharedItem::Several(items),
+        }
+    }
+}
+
+/// Why a line of the input is not a usable rucksack.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LintIssue {
+    Empty,
+    OddLength(usize),
+    InvalidItem { column: usize, item: char },
+    NoSharedItem,
+    SeveralSharedItems(ItemSet),
+}
+
+impl LintIssue {
+    /// Column the issue points at, counting from 1.
+    pub fn column(&self) -> usize {
+        match self {
+            LintIssue::InvalidItem { column, .. } => *column,
+            _ => 1,
+        }
+    }
+}
+
+impl Display for LintIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LintIssue::Empty => write!(f, "empty rucksack"),
+            LintIssue::OddLength(n) => write!(
+                f,
+                "odd number of items ({}), compartments cannot be equal",
+                n
+            ),
+            LintIssue::InvalidItem { column, item } => {
+                write!(f, "invalid item {:?} at column {}", item, column)
+            }
+            LintIssue::NoSharedItem => write!(f, "no item shared between compartments"),
+            LintIssue::SeveralSharedItems(items) => write!(
+                f,
+                "items {} are all shared between compartments",
+                items.iter().filter_map(|p| p.item()).collect::<String>()
+            ),
+        }
+    }
+}
+
+/// A malformed input line and what is wrong with it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LintFinding {
+    pub line: usize,
+    pub issue: LintIssue,
+}
+
+impl Display for LintFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.issue)
+    }
+}
+
+/// Every problem with every line, unlike [`read_rucksacks`] which stops at the first. Shared
+/// items are only checked on lines that are otherwise well formed.
+pub fn lint_rucksacks<R>(reader: R) -> Result<Vec<LintFinding>, AocError>
+where
+    R: BufRead,
+{
+    let mut findings = Vec::new();
+    for (line_idx, line) in reader.lines().enumerate() {
+        let rucksack = Rucksack::new(line_idx + 1, line?);
+        let mut issues = rucksack.problems();
+        if issues.is_empty() {
+            match SharedItem::from(rucksack.shared_items()) {
+                SharedItem::One(_) => {}
+                SharedItem::None => issues.push(LintIssue::NoSharedItem),
+                SharedItem::Several(items) => issues.push(LintIssue::SeveralSharedItems(items)),
+            }
+        }
+        findings.extend(issues.into_iter().map(|issue| LintFinding {
+            line: rucksack.line,
+            issue,
+        }));
+    }
+    Ok(findings)
+}
+
+/// How consecutive rucksacks are grouped to look for badges.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Grouping {
+    /// Non-overlapping groups of this many rucksacks; the count must divide evenly.
+    Chunks(usize),
+    /// Every run of this many consecutive rucksacks.
+    Windows(usize),
+}
+
+/// The badge candidates of the group spanning input lines `first_line..=last_line`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct GroupBadge {
+    pub first_line: usize,
+    pub last_line: usize,
+    pub badge: SharedItem,
+}
+
+/// Badges of every group; chunked groupings reject an incomplete trailing group.
+pub fn group_badges(
+    rucksacks: &[Rucksack],
+    grouping: Grouping,
+) -> Result<Vec<GroupBadge>, AocError> {
+    let badge = |group: &[Rucksack]| GroupBadge {
+        first_line: group[0].line,
+        last_line: group[group.len() - 1].line,
+        badge: group[0].shared_with(&group[1..]).into(),
+    };
+    match grouping {
+        Grouping::Chunks(0) | Grouping::Windows(0) => Err(AocError::no_solution(
+            Day3::DAY,
+            "groups need at least one rucksack",
+        )),
+        Grouping::Chunks(size) => {
+            let trailing = rucksacks.len() % size;
+            if trailing != 0 {
+                let first = &rucksacks[rucksacks.len() - trailing];
+                return Err(AocError::parse(
+                    Day3::DAY,
+                    first.line,
+                    1,
+                    &first.content,
+                    format!(
+                        "incomplete trailing group: {} rucksack(s) left over, groups need {}",
+                        trailing, size
+                    ),
+                ));
+            }
+            Ok(rucksacks.chunks(size).map(badge).collect())
+        }
+        Grouping::Windows(size) => Ok(rucksacks.windows(size).map(badge).collect()),
     }
 }
 
-#[derive(Copy, Clone, Debug)]
-struct Priority(u32);
+/// A set of items, stored as a bitmask where bit `p` marks the item with priority `p`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub fn new() -> Self {
+        ItemSet(0)
+    }
+
+    /// The set of item letters in `items`; anything that is not a letter is ignored.
+    pub fn from_items(items: &[u8]) -> Self {
+        items
+            .iter()
+            .filter_map(|item| Priority::try_from(char::from(*item)).ok())
+            .collect()
+    }
+
+    /// Adds the item with `priority`; a priority no item has, such as a sum, is ignored.
+    pub fn insert(&mut self, priority: Priority) {
+        self.0 |= Self::bit(priority);
+    }
+
+    pub fn contains(&self, priority: Priority) -> bool {
+        self.0 & Self::bit(priority) != 0
+    }
+
+    fn bit(priority: Priority) -> u64 {
+        if priority.item().is_some() {
+            1 << priority.0
+        } else {
+            0
+        }
+    }
+
+    pub fn intersection(&self, other: &ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+
+    pub fn union(&self, other: &ItemSet) -> ItemSet {
+        ItemSet(self.0 | other.0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Items in increasing priority.
+    pub fn iter(&self) -> impl Iterator<Item = Priority> {
+        let bits = self.0;
+        (1..=52).filter(move |p| bits & (1 << p) != 0).map(Priority)
+    }
+}
+
+impl BitAnd for ItemSet {
+    type Output = Self;
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.intersection(&rhs)
+    }
+}
+
+impl BitOr for ItemSet {
+    type Output = Self;
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(&rhs)
+    }
+}
+
+impl FromIterator<Priority> for ItemSet {
+    fn from_iter<I: IntoIterator<Item = Priority>>(iter: I) -> Self {
+        let mut set = ItemSet::new();
+        for priority in iter {
+            set.insert(priority);
+        }
+        set
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Priority(u32);
+
+impl Priority {
+    pub fn value(&self) -> u32 {
+        self.0
+    }
+}
+
+impl Priority {
+    /// The item letter with this priority; `None` past 52, as for a summed priority.
+    pub fn item(&self) -> Option<char> {
+        match self.0 {
+            1..=26 => Some(char::from(b'a' + self.0 as u8 - 1)),
+            27..=52 => Some(char::from(b'A' + self.0 as u8 - 27)),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
 
 impl TryFrom<char> for Priority {
     type Error = &'static str;
     fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
-            'a'..='z' => Ok(Priority(((value as u8) - ('a' as u8) + 1) as u32)),
-            'A'..='Z' => Ok(Priority(((value as u8) - ('A' as u8) + 27) as u32)),
+            'a'..='z' => Ok(Priority(((value as u8) - b'a' + 1) as u32)),
+            'A'..='Z' => Ok(Priority(((value as u8) - b'A' + 27) as u32)),
             _ => Err("Invalid item letter for conversion"),
         }
     }
@@ -76,46 +410,252 @@ impl Sum for Priority {
     }
 }
 
-fn rucksack_priorities<R>(reader: R) -> Priority
+pub fn read_rucksacks<R>(reader: R) -> Result<Vec<Rucksack>, AocError>
 where
     R: BufRead,
 {
     reader
         .lines()
-        .map(|line| Rucksack {
-            content: line.unwrap(),
+        .enumerate()
+        .map(|(line_idx, line)| {
+            let rucksack = Rucksack::new(line_idx + 1, line?);
+            match rucksack.problems().first() {
+                Some(issue) => Err(AocError::parse(
+                    Day3::DAY,
+                    rucksack.line,
+                    issue.column(),
+                    &rucksack.content,
+                    issue.to_string(),
+                )),
+                None => Ok(rucksack),
+            }
         })
+        .collect()
+}
+
+pub fn rucksack_priorities(rucksacks: &[Rucksack]) -> Result<Priority, AocError> {
+    rucksacks
+        .iter()
         .map(|rucksack| {
-            Priority::try_from(char::try_from(rucksack.find_shared_item()).unwrap()).unwrap()
+            SharedItem::from(rucksack.shared_items())
+                .single(|| format!("between compartments on line {}", rucksack.line))
         })
         .sum()
 }
 
-fn rucksack_group_priorities<R>(reader: R) -> Priority
-where
-    R: BufRead,
-{
-    const GROUP_SIZE: usize = 3;
-    let group_index = (0..GROUP_SIZE).into_iter().cycle();
-    reader
-        .lines()
-        .map(|line| Rucksack {
-            content: line.unwrap(),
+pub fn rucksack_group_priorities(rucksacks: &[Rucksack]) -> Result<Priority, AocError> {
+    group_badges(rucksacks, Grouping::Chunks(GROUP_SIZE))?
+        .into_iter()
+        .map(|group| {
+            group
+                .badge
+                .single(|| format!("by group on lines {}-{}", group.first_line, group.last_line))
         })
-        .zip(group_index)
-        .fold(
-            (Priority(0), [None, None, None]),
-            |(mut p, mut group): (Priority, [Option<Rucksack>; GROUP_SIZE]), (r, index)| {
-                group[index] = Some(r);
-                if index == GROUP_SIZE - 1 {
-                    let shared_item = group[0].as_ref().unwrap().find_shared_by_group(
-                        group[1].as_ref().unwrap(),
-                        group[2].as_ref().unwrap(),
-                    );
-                    p = p + Priority::try_from(char::try_from(shared_item).unwrap()).unwrap();
-                }
-                (p, group)
-            },
-        )
-        .0
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw
+";
+
+    #[test]
+    fn example_priorities() {
+        let rucksacks = read_rucksacks(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(rucksack_priorities(&rucksacks).unwrap(), Priority(157));
+    }
+
+    #[test]
+    fn example_group_priorities() {
+        let rucksacks = read_rucksacks(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(rucksack_group_priorities(&rucksacks).unwrap(), Priority(70));
+    }
+
+    #[test]
+    fn priority_from_letter() {
+        assert_eq!(Priority::try_from('a'), Ok(Priority(1)));
+        assert_eq!(Priority::try_from('Z'), Ok(Priority(52)));
+        assert_eq!(Priority(52).item(), Some('Z'));
+        assert_eq!(Priority(157).item(), None);
+        let mut set = ItemSet::new();
+        set.insert(Priority(157));
+        assert!(set.is_empty());
+        assert!(!set.contains(Priority(157)));
+        assert!(Priority::try_from('1').is_err());
+    }
+
+    #[test]
+    fn item_set_operations() {
+        let a = ItemSet::from_items(b"abcZ");
+        let b = ItemSet::from_items(b"cZzz");
+        assert_eq!(a.len(), 4);
+        assert_eq!(b.len(), 3);
+        assert_eq!(
+            (a & b).iter().filter_map(|p| p.item()).collect::<String>(),
+            "cZ"
+        );
+        assert_eq!(a | b, ItemSet::from_items(b"abczZ"));
+        assert!(a.contains(Priority(52)));
+        assert!(!a.contains(Priority(26)));
+        assert!((a & ItemSet::from_items(b"xy")).is_empty());
+        assert_eq!(
+            a.iter().collect::<Vec<Priority>>(),
+            [Priority(1), Priority(2), Priority(3), Priority(52)]
+        );
+    }
+
+    #[test]
+    fn example_shared_items() {
+        let rucksacks = read_rucksacks(EXAMPLE.as_bytes()).unwrap();
+        let shared = rucksacks
+            .iter()
+            .map(|r| {
+                r.shared_items()
+                    .iter()
+                    .filter_map(|p| p.item())
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>();
+        assert_eq!(shared, ["p", "L", "P", "v", "t", "s"]);
+        let badge = rucksacks[0].shared_with(&rucksacks[1..3]);
+        assert_eq!(badge.iter().collect::<Vec<Priority>>(), [Priority(18)]);
+    }
+
+    #[test]
+    fn example_group_badges() {
+        let rucksacks = read_rucksacks(EXAMPLE.as_bytes()).unwrap();
+        let groups = group_badges(&rucksacks, Grouping::Chunks(GROUP_SIZE)).unwrap();
+        assert_eq!(
+            groups,
+            [
+                GroupBadge {
+                    first_line: 1,
+                    last_line: 3,
+                    badge: SharedItem::One(Priority(18)),
+                },
+                GroupBadge {
+                    first_line: 4,
+                    last_line: 6,
+                    badge: SharedItem::One(Priority(52)),
+                },
+            ]
+        );
+
+        let windows = group_badges(&rucksacks, Grouping::Windows(2)).unwrap();
+        assert_eq!(windows.len(), 5);
+        assert_eq!((windows[4].first_line, windows[4].last_line), (5, 6));
+        assert_eq!(
+            windows[0].badge,
+            SharedItem::Several(ItemSet::from_items(b"rsFMf"))
+        );
+        assert_eq!(windows[0].badge.priority(), None);
+
+        let whole = group_badges(&rucksacks, Grouping::Chunks(6)).unwrap();
+        assert_eq!(whole[0].badge, SharedItem::None);
+    }
+
+    #[test]
+    fn incomplete_trailing_group_is_rejected() {
+        let rucksacks = read_rucksacks(EXAMPLE.as_bytes()).unwrap();
+        assert!(matches!(
+            group_badges(&rucksacks[..5], Grouping::Chunks(GROUP_SIZE)),
+            Err(AocError::Parse { line: 4, .. })
+        ));
+        assert!(matches!(
+            rucksack_group_priorities(&rucksacks[..4]),
+            Err(AocError::Parse { line: 4, .. })
+        ));
+        assert!(group_badges(&rucksacks, Grouping::Windows(0)).is_err());
+        assert!(group_badges(&rucksacks, Grouping::Windows(7))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn several_shared_items_are_no_solution() {
+        let rucksacks = read_rucksacks("abab\n".as_bytes()).unwrap();
+        assert_eq!(rucksacks[0].shared_items().len(), 2);
+        assert!(matches!(
+            rucksack_priorities(&rucksacks),
+            Err(AocError::NoSolution { .. })
+        ));
+    }
+
+    #[test]
+    fn missing_shared_item_is_no_solution() {
+        let rucksacks = read_rucksacks("abcd\n".as_bytes()).unwrap();
+        assert!(matches!(
+            rucksack_priorities(&rucksacks),
+            Err(AocError::NoSolution { .. })
+        ));
+    }
+
+    #[test]
+    fn malformed_lines_are_rejected() {
+        match read_rucksacks("abcd\nab1d\n".as_bytes()) {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (2, 3)),
+            other => panic!("unexpected result {:?}", other.map(|r| r.len())),
+        }
+        assert!(matches!(
+            read_rucksacks("abcd\nabcab\n".as_bytes()),
+            Err(AocError::Parse { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn lint_reports_every_malformed_line() {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp\nabcab\n\nab1d2\nabcd\nabab\n";
+        let findings = lint_rucksacks(input.as_bytes()).unwrap();
+        assert_eq!(
+            findings,
+            [
+                LintFinding {
+                    line: 2,
+                    issue: LintIssue::OddLength(5),
+                },
+                LintFinding {
+                    line: 3,
+                    issue: LintIssue::Empty,
+                },
+                LintFinding {
+                    line: 4,
+                    issue: LintIssue::OddLength(5),
+                },
+                LintFinding {
+                    line: 4,
+                    issue: LintIssue::InvalidItem {
+                        column: 3,
+                        item: '1',
+                    },
+                },
+                LintFinding {
+                    line: 4,
+                    issue: LintIssue::InvalidItem {
+                        column: 5,
+                        item: '2',
+                    },
+                },
+                LintFinding {
+                    line: 5,
+                    issue: LintIssue::NoSharedItem,
+                },
+                LintFinding {
+                    line: 6,
+                    issue: LintIssue::SeveralSharedItems(ItemSet::from_items(b"ab")),
+                },
+            ]
+        );
+        assert_eq!(
+            findings[6].to_string(),
+            "line 6: items ab are all shared between compartments"
+        );
+        assert!(lint_rucksacks(EXAMPLE.as_bytes()).unwrap().is_empty());
+    }
 }
@@ -1,88 +1,302 @@
 // mod day4
 
 use std::{
-    io::{BufRead, BufReader},
-    ops::RangeInclusive,
-    str::FromStr, fs::File,
+    fmt::{self, Display},
+    io::BufRead,
+    str::FromStr,
 };
 
-struct RangePairing(RangeInclusive<i32>, RangeInclusive<i32>);
+use super::Solution;
+use crate::error::AocError;
 
-impl RangePairing {
+pub mod index;
+pub mod interval;
+
+use interval::{Discrete, Interval};
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+    type Input = Vec<RangePairing>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Self::Input, AocError> {
+        read_pairings(reader)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Answer1, AocError> {
+        Ok(find_fully_contained(input))
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Self::Answer2, AocError> {
+        Ok(find_overlapping(input))
+    }
+}
+
+/// Two elves' section assignments; sections are numbered with `T`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RangePairing<T = i32>(pub Interval<T>, pub Interval<T>);
+
+impl<T: Ord + Copy> RangePairing<T> {
     pub fn full_overlap(&self) -> bool {
-        (self.0.start() >= self.1.start() && self.0.end() <= self.1.end())
-            || (self.1.start() >= self.0.start() && self.1.end() <= self.0.end())
+        self.0.covers(&self.1) || self.1.covers(&self.0)
     }
 
     pub fn overlap(&self) -> bool {
-        ((self.0.start() <= self.1.end()) && (self.0.start() >= self.1.start())) ||
-        ((self.0.end() >= self.1.start()) && (self.0.end() <= self.1.end())) ||
-        self.full_overlap()
+        self.0.overlaps(&self.1)
+    }
+
+    /// The sections both elves were assigned.
+    pub fn overlap_section(&self) -> Option<Interval<T>> {
+        self.0.intersection(&self.1)
+    }
+}
+
+/// Why a line is not a pairing, and the column (counting from 1) where the problem starts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PairingError {
+    pub column: usize,
+    pub reason: String,
+}
+
+impl Display for PairingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "column {}: {}", self.column, self.reason)
     }
 }
 
-impl FromStr for RangePairing {
-    type Err = &'static str;
+/// Parses `start-end` found at byte `offset` of the line. The dash is searched after the first
+/// character so negative starts work for signed bounds.
+fn parse_interval<T>(text: &str, offset: usize) -> Result<Interval<T>, PairingError>
+where
+    T: FromStr + Ord + Copy,
+    T::Err: Display,
+{
+    let dash = text
+        .char_indices()
+        .skip(1)
+        .find(|(_, c)| *c == '-')
+        .map(|(idx, _)| idx)
+        .ok_or_else(|| PairingError {
+            column: offset + 1,
+            reason: format!("expected start-end, found '{}'", text),
+        })?;
+    let bound = |text: &str, offset: usize| {
+        T::from_str(text).map_err(|e| PairingError {
+            column: offset + 1,
+            reason: format!("invalid section '{}': {}", text, e),
+        })
+    };
+    let start = bound(&text[..dash], offset)?;
+    let end = bound(&text[dash + 1..], offset + dash + 1)?;
+    Interval::new(start, end).ok_or_else(|| PairingError {
+        column: offset + 1,
+        reason: format!("inverted range {}, start is after end", text),
+    })
+}
+
+impl<T> FromStr for RangePairing<T>
+where
+    T: FromStr + Ord + Copy,
+    T::Err: Display,
+{
+    type Err = PairingError;
     fn from_str(value: &str) -> Result<Self, Self::Err> {
-        let ranges_pairs = value.split_once(',');
-        let ranges_bounds = match ranges_pairs {
-            Some((r1, r2)) => (r1.split_once('-'), r2.split_once('-')),
-            _ => return Err("Unexpected string line data"),
-        };
-        let ranges_bounds_parsed = match ranges_bounds {
-            (Some((r1_low, r1_upper)), Some((r2_low, r2_upper))) => (
-                (i32::from_str(r1_low).ok(), i32::from_str(r1_upper).ok()),
-                (i32::from_str(r2_low).ok(), i32::from_str(r2_upper).ok()),
-            ),
-            _ => return Err("Unexpected string line data"),
-        };
-
-        match ranges_bounds_parsed {
-            ((Some(r1_low), Some(r1_upper)), (Some(r2_low), Some(r2_upper))) => {
-                Ok(RangePairing(r1_low..=r1_upper, r2_low..=r2_upper))
-            }
-            _ => return Err("Unexpected string line data"),
-        }
+        let (first, second) = value.split_once(',').ok_or_else(|| PairingError {
+            column: 1,
+            reason: "expected two assignments separated by ','".to_owned(),
+        })?;
+        Ok(RangePairing(
+            parse_interval(first, 0)?,
+            parse_interval(second, first.len() + 1)?,
+        ))
     }
 }
 
-fn find_fully_contained<R>(buf_reader: R) -> usize
+/// What to do with lines that are not pairings.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Stop at the first malformed line.
+    Strict,
+    /// Skip malformed lines, keeping their errors.
+    Lenient,
+}
+
+/// The pairings of an input, and the errors of any lines skipped in lenient mode.
+#[derive(Debug)]
+pub struct Pairings<T = i32> {
+    pub pairs: Vec<RangePairing<T>>,
+    pub skipped: Vec<AocError>,
+}
+
+pub fn parse_pairings<T, R>(buf_reader: R, mode: ParseMode) -> Result<Pairings<T>, AocError>
 where
+    T: FromStr + Ord + Copy,
+    T::Err: Display,
     R: BufRead,
 {
-    buf_reader
-        .lines()
-        .filter_map(|line| match line.ok() {
-            Some(line_str) => RangePairing::from_str(&line_str).ok(),
-            _ => None,
-        })
-        .filter(|pair| pair.full_overlap())
-        .count()
+    let mut pairings = Pairings {
+        pairs: Vec::new(),
+        skipped: Vec::new(),
+    };
+    for (line_idx, line) in buf_reader.lines().enumerate() {
+        let line = line?;
+        match RangePairing::from_str(&line) {
+            Ok(pair) => pairings.pairs.push(pair),
+            Err(e) => {
+                let err = AocError::parse(Day4::DAY, line_idx + 1, e.column, &line, e.reason);
+                match mode {
+                    ParseMode::Strict => return Err(err),
+                    ParseMode::Lenient => pairings.skipped.push(err),
+                }
+            }
+        }
+    }
+    Ok(pairings)
 }
 
-fn find_overlapping<R>(buf_reader: R) -> usize
+/// Parses every line as an `i32` pairing, failing on the first malformed one.
+pub fn read_pairings<R>(buf_reader: R) -> Result<Vec<RangePairing>, AocError>
 where
     R: BufRead,
 {
-    buf_reader
-        .lines()
-        .filter_map(|line| match line.ok() {
-            Some(line_str) => RangePairing::from_str(&line_str).ok(),
-            _ => None,
-        })
-        .filter(|pair| pair.overlap())
-        .count()
+    parse_pairings(buf_reader, ParseMode::Strict).map(|pairings| pairings.pairs)
+}
+
+pub fn find_fully_contained<T: Ord + Copy>(pairings: &[RangePairing<T>]) -> usize {
+    pairings.iter().filter(|pair| pair.full_overlap()).count()
+}
+
+pub fn find_overlapping<T: Ord + Copy>(pairings: &[RangePairing<T>]) -> usize {
+    pairings.iter().filter(|pair| pair.overlap()).count()
+}
+
+/// The fewest intervals covering every section assigned to any elf.
+pub fn coverage<T: Discrete>(pairings: &[RangePairing<T>]) -> Vec<Interval<T>> {
+    interval::merge(pairings.iter().flat_map(|pair| [pair.0, pair.1]))
+}
+
+/// Sections between the first and last assigned ones that no elf was given.
+pub fn uncovered_sections<T: Discrete>(pairings: &[RangePairing<T>]) -> Vec<Interval<T>> {
+    let covering = coverage(pairings);
+    match (covering.first(), covering.last()) {
+        (Some(first), Some(last)) => {
+            let span = Interval::new(first.start(), last.end()).expect("merge keeps order");
+            interval::uncovered(covering, span)
+        }
+        _ => Vec::new(),
+    }
 }
 
+pub fn print_answer() -> Result<(), AocError> {
+    super::print_solution(&Day4)
+}
 
-pub fn print_answer() {
-    println!(
-        "{:?}",
-        find_fully_contained(BufReader::new(File::open("data/input_day4").unwrap()))
-    );
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    println!(
-        "{:?}",
-        find_overlapping(BufReader::new(File::open("data/input_day4").unwrap()))
-    );
+    pub(super) const EXAMPLE: &str = "2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8
+";
+
+    #[test]
+    fn example_fully_contained() {
+        let pairings = read_pairings(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(find_fully_contained(&pairings), 2);
+    }
+
+    #[test]
+    fn example_overlapping() {
+        let pairings = read_pairings(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(find_overlapping(&pairings), 4);
+    }
+
+    pub(super) fn iv(start: i32, end: i32) -> Interval<i32> {
+        Interval::new(start, end).unwrap()
+    }
+
+    #[test]
+    fn example_overlap_sections() {
+        let pairings = read_pairings(EXAMPLE.as_bytes()).unwrap();
+        let sections = pairings
+            .iter()
+            .map(RangePairing::overlap_section)
+            .collect::<Vec<Option<Interval<i32>>>>();
+        assert_eq!(
+            sections,
+            [
+                None,
+                None,
+                Some(iv(7, 7)),
+                Some(iv(3, 7)),
+                Some(iv(6, 6)),
+                Some(iv(4, 6))
+            ]
+        );
+        assert_eq!(coverage(&pairings), [iv(2, 9)]);
+        assert!(uncovered_sections(&pairings).is_empty());
+    }
+
+    #[test]
+    fn uncovered_sections_between_assignments() {
+        let pairings = read_pairings("1-3,9-9\n5-5,2-3\n".as_bytes()).unwrap();
+        assert_eq!(coverage(&pairings), [iv(1, 3), iv(5, 5), iv(9, 9)]);
+        assert_eq!(uncovered_sections(&pairings), [iv(4, 4), iv(6, 8)]);
+        assert!(uncovered_sections::<i32>(&[]).is_empty());
+    }
+
+    #[test]
+    fn parse_pairing() {
+        assert_eq!(
+            RangePairing::from_str("2-4,6-8"),
+            Ok(RangePairing(iv(2, 4), iv(6, 8)))
+        );
+        assert!(RangePairing::<i32>::from_str("2-4;6-8").is_err());
+        assert_eq!(
+            RangePairing::<i64>::from_str("-4--2,3-3"),
+            Ok(RangePairing(
+                Interval::new(-4, -2).unwrap(),
+                Interval::point(3)
+            ))
+        );
+        assert_eq!(
+            RangePairing::<u8>::from_str("2-4,6-300")
+                .unwrap_err()
+                .column,
+            7
+        );
+        assert_eq!(
+            RangePairing::<u8>::from_str("2-4,7-3"),
+            Err(PairingError {
+                column: 5,
+                reason: "inverted range 7-3, start is after end".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn malformed_lines_in_strict_and_lenient_mode() {
+        let input = "2-4,6-8\n2-3,x-5\n5-7,7-9\n7-3,1-1\n";
+        match read_pairings(input.as_bytes()) {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (2, 5)),
+            other => panic!("unexpected result {:?}", other),
+        }
+        let pairings = parse_pairings::<u16, _>(input.as_bytes(), ParseMode::Lenient).unwrap();
+        assert_eq!(pairings.pairs.len(), 2);
+        assert!(matches!(
+            pairings.skipped[..],
+            [
+                AocError::Parse { line: 2, .. },
+                AocError::Parse { line: 4, .. }
+            ]
+        ));
+        assert_eq!(find_overlapping(&pairings.pairs), 1);
+    }
 }
@@ -0,0 +1,268 @@
+//! An interval tree over every elf's assignment, for questions that span the whole input rather
+//! than a single pair.
+
+use std::cmp::max;
+
+use super::interval::Interval;
+use super::RangePairing;
+
+/// Intervals with attached values, answering overlap queries in `O(log n + matches)`.
+///
+/// The entries are kept sorted by start and viewed as an implicit balanced tree: the middle
+/// entry of every range is the root of that range, and `max_end` holds the largest end in the
+/// subtree rooted there, so whole subtrees ending before a query are skipped.
+#[derive(Clone, Debug)]
+pub struct IntervalTree<T, V> {
+    entries: Vec<(Interval<T>, V)>,
+    max_end: Vec<T>,
+}
+
+impl<T: Ord + Copy, V> IntervalTree<T, V> {
+    pub fn new(mut entries: Vec<(Interval<T>, V)>) -> Self {
+        entries.sort_by_key(|(interval, _)| (interval.start(), interval.end()));
+        let mut max_end = entries
+            .iter()
+            .map(|(interval, _)| interval.end())
+            .collect::<Vec<T>>();
+        Self::fill_max_end(&entries, &mut max_end, 0, entries.len());
+        IntervalTree { entries, max_end }
+    }
+
+    fn fill_max_end(entries: &[(Interval<T>, V)], max_end: &mut [T], lo: usize, hi: usize) {
+        if lo >= hi {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        Self::fill_max_end(entries, max_end, lo, mid);
+        Self::fill_max_end(entries, max_end, mid + 1, hi);
+        let mut end = entries[mid].0.end();
+        if lo < mid {
+            end = max(end, max_end[lo + (mid - lo) / 2]);
+        }
+        if mid + 1 < hi {
+            end = max(end, max_end[mid + 1 + (hi - mid - 1) / 2]);
+        }
+        max_end[mid] = end;
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Entries whose interval shares a value with `query`, ordered by start.
+    pub fn overlapping(&self, query: &Interval<T>) -> Vec<&(Interval<T>, V)> {
+        let mut found = Vec::new();
+        self.collect(query, 0, self.entries.len(), &mut found);
+        found
+    }
+
+    /// Entries whose interval contains `value`, ordered by start.
+    pub fn containing(&self, value: T) -> Vec<&(Interval<T>, V)> {
+        self.overlapping(&Interval::point(value))
+    }
+
+    fn collect<'a>(
+        &'a self,
+        query: &Interval<T>,
+        lo: usize,
+        hi: usize,
+        found: &mut Vec<&'a (Interval<T>, V)>,
+    ) {
+        if lo >= hi {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        if self.max_end[mid] < query.start() {
+            return;
+        }
+        self.collect(query, lo, mid, found);
+        let entry = &self.entries[mid];
+        if entry.0.start() > query.end() {
+            // Everything to the right starts even later.
+            return;
+        }
+        if entry.0.overlaps(query) {
+            found.push(entry);
+        }
+        self.collect(query, mid + 1, hi, found);
+    }
+
+    /// The largest number of intervals sharing a single value, and the first value where that
+    /// many meet; `None` when empty.
+    pub fn max_depth(&self) -> Option<(T, usize)> {
+        // Starts sort before ends at the same value because intervals are closed.
+        let mut events = self
+            .entries
+            .iter()
+            .flat_map(|(interval, _)| [(interval.start(), false), (interval.end(), true)])
+            .collect::<Vec<(T, bool)>>();
+        events.sort_unstable();
+        let mut depth = 0;
+        let mut deepest = None;
+        for (value, is_end) in events {
+            if is_end {
+                depth -= 1;
+            } else {
+                depth += 1;
+                if deepest.is_none_or(|(_, d)| depth > d) {
+                    deepest = Some((value, depth));
+                }
+            }
+        }
+        deepest
+    }
+}
+
+/// One elf: the index of its pair in the input and whether it is listed first (0) or second (1).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ElfId {
+    pub pair: usize,
+    pub elf: usize,
+}
+
+/// Every assignment of an input, indexed by section.
+#[derive(Clone, Debug)]
+pub struct SectionIndex<T = i32> {
+    pairings: Vec<RangePairing<T>>,
+    tree: IntervalTree<T, ElfId>,
+}
+
+impl<T: Ord + Copy> SectionIndex<T> {
+    pub fn new(pairings: &[RangePairing<T>]) -> Self {
+        let entries = pairings
+            .iter()
+            .enumerate()
+            .flat_map(|(pair, pairing)| {
+                [
+                    (pairing.0, ElfId { pair, elf: 0 }),
+                    (pairing.1, ElfId { pair, elf: 1 }),
+                ]
+            })
+            .collect();
+        SectionIndex {
+            pairings: pairings.to_vec(),
+            tree: IntervalTree::new(entries),
+        }
+    }
+
+    pub fn assignment(&self, id: ElfId) -> Option<Interval<T>> {
+        let pairing = self.pairings.get(id.pair)?;
+        match id.elf {
+            0 => Some(pairing.0),
+            1 => Some(pairing.1),
+            _ => None,
+        }
+    }
+
+    /// Elves assigned `section`, in input order.
+    pub fn covering(&self, section: T) -> Vec<ElfId> {
+        Self::ids(self.tree.containing(section))
+    }
+
+    /// Elves whose assignment shares a section with `sections`, in input order.
+    pub fn overlapping(&self, sections: &Interval<T>) -> Vec<ElfId> {
+        Self::ids(self.tree.overlapping(sections))
+    }
+
+    /// Other pairs with an elf whose assignment overlaps either elf of pair `pair`, in input
+    /// order; `None` if there is no such pair.
+    pub fn pairs_overlapping(&self, pair: usize) -> Option<Vec<usize>> {
+        let pairing = self.pairings.get(pair)?;
+        let mut pairs = [pairing.0, pairing.1]
+            .iter()
+            .flat_map(|sections| self.overlapping(sections))
+            .map(|id| id.pair)
+            .filter(|other| *other != pair)
+            .collect::<Vec<usize>>();
+        pairs.sort_unstable();
+        pairs.dedup();
+        Some(pairs)
+    }
+
+    /// The first section assigned to the most elves, and how many that is.
+    pub fn busiest_section(&self) -> Option<(T, usize)> {
+        self.tree.max_depth()
+    }
+
+    fn ids(entries: Vec<&(Interval<T>, ElfId)>) -> Vec<ElfId> {
+        let mut ids = entries
+            .into_iter()
+            .map(|(_, id)| *id)
+            .collect::<Vec<ElfId>>();
+        ids.sort_unstable();
+        ids
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::EXAMPLE;
+    use super::super::{parse_pairings, read_pairings, ParseMode};
+    use super::*;
+
+    #[test]
+    fn tree_matches_linear_scan() {
+        let intervals = (0..200)
+            .map(|i: i32| {
+                let start = (i * 37) % 101;
+                Interval::new(start, start + (i * 13) % 17).unwrap()
+            })
+            .collect::<Vec<Interval<i32>>>();
+        let tree = IntervalTree::new(intervals.iter().copied().zip(0..).collect());
+        for query in [(0, 0), (5, 9), (50, 50), (100, 130), (-5, -1), (117, 120)] {
+            let query = Interval::new(query.0, query.1).unwrap();
+            let mut found = tree
+                .overlapping(&query)
+                .iter()
+                .map(|(_, i)| *i)
+                .collect::<Vec<usize>>();
+            found.sort_unstable();
+            let expected = (0..intervals.len())
+                .filter(|i| intervals[*i].overlaps(&query))
+                .collect::<Vec<usize>>();
+            assert_eq!(found, expected, "query {}", query);
+        }
+        assert!(IntervalTree::<i32, ()>::new(Vec::new())
+            .max_depth()
+            .is_none());
+    }
+
+    #[test]
+    fn example_queries() {
+        let pairings = read_pairings(EXAMPLE.as_bytes()).unwrap();
+        let index = SectionIndex::new(&pairings);
+        assert_eq!(
+            index.covering(8),
+            [
+                ElfId { pair: 0, elf: 1 },
+                ElfId { pair: 2, elf: 1 },
+                ElfId { pair: 3, elf: 0 },
+                ElfId { pair: 5, elf: 1 },
+            ]
+        );
+        assert!(index.covering(10).is_empty());
+        assert_eq!(index.pairs_overlapping(1), Some(vec![0, 2, 3, 4, 5]));
+        assert_eq!(index.pairs_overlapping(6), None);
+        assert_eq!(index.busiest_section(), Some((6, 8)));
+        assert_eq!(
+            index.assignment(ElfId { pair: 4, elf: 0 }),
+            Interval::new(6, 6)
+        );
+    }
+
+    #[test]
+    fn wide_sections() {
+        let input = "1-18446744073709551615,5-5\n18446744073709551610-18446744073709551612,1-2\n";
+        let pairings = parse_pairings::<u64, _>(input.as_bytes(), ParseMode::Strict).unwrap();
+        let index = SectionIndex::new(&pairings.pairs);
+        assert_eq!(
+            index.covering(u64::MAX - 4),
+            [ElfId { pair: 0, elf: 0 }, ElfId { pair: 1, elf: 0 }]
+        );
+        assert_eq!(index.busiest_section(), Some((1, 2)));
+    }
+}
@@ -0,0 +1,203 @@
+//! Closed intervals of ordered values, with the set operations needed to reason about section
+//! assignments rather than just test them.
+
+use std::cmp::{max, min};
+use std::fmt::{self, Display};
+
+/// Values with a successor and predecessor, so intervals that merely touch can be merged and
+/// removing one interval from another leaves closed pieces.
+pub trait Discrete: Copy + Ord {
+    fn succ(self) -> Option<Self>;
+    fn pred(self) -> Option<Self>;
+    /// Number of values in `low..=high`, saturating at `u64::MAX`.
+    fn count(low: Self, high: Self) -> u64;
+}
+
+macro_rules! impl_discrete {
+    ($($t:ty),*) => {
+        $(
+            impl Discrete for $t {
+                fn succ(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn pred(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                fn count(low: Self, high: Self) -> u64 {
+                    (high as i128 - low as i128 + 1).clamp(0, u64::MAX as i128) as u64
+                }
+            }
+        )*
+    };
+}
+
+impl_discrete!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// The values from `start` to `end`, both included; never empty.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    start: T,
+    end: T,
+}
+
+impl<T: Ord + Copy> Interval<T> {
+    /// `None` if `start` is after `end`.
+    pub fn new(start: T, end: T) -> Option<Self> {
+        (start <= end).then_some(Interval { start, end })
+    }
+
+    pub fn point(value: T) -> Self {
+        Interval {
+            start: value,
+            end: value,
+        }
+    }
+
+    pub fn start(&self) -> T {
+        self.start
+    }
+
+    pub fn end(&self) -> T {
+        self.end
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    /// Whether every value of `other` is also in `self`.
+    pub fn covers(&self, other: &Interval<T>) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    pub fn overlaps(&self, other: &Interval<T>) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    pub fn intersection(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        Interval::new(max(self.start, other.start), min(self.end, other.end))
+    }
+}
+
+impl<T: Discrete> Interval<T> {
+    // An interval always holds at least one value, so there is no `is_empty` to pair with this.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> u64 {
+        T::count(self.start, self.end)
+    }
+
+    /// Whether the two intervals overlap or one starts right after the other ends.
+    pub fn touches(&self, other: &Interval<T>) -> bool {
+        self.overlaps(other)
+            || self.end.succ() == Some(other.start)
+            || other.end.succ() == Some(self.start)
+    }
+
+    /// The single interval holding exactly the values of both, if there is one.
+    pub fn union(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        self.touches(other).then(|| Interval {
+            start: min(self.start, other.start),
+            end: max(self.end, other.end),
+        })
+    }
+
+    /// The values of `self` not in `other`: none, one or two intervals, in order.
+    pub fn difference(&self, other: &Interval<T>) -> Vec<Interval<T>> {
+        if !self.overlaps(other) {
+            return vec![*self];
+        }
+        let before = other
+            .start
+            .pred()
+            .and_then(|end| Interval::new(self.start, end));
+        let after = other
+            .end
+            .succ()
+            .and_then(|start| Interval::new(start, self.end));
+        before.into_iter().chain(after).collect()
+    }
+}
+
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+/// The fewest disjoint, non-touching intervals covering the same values as `intervals`, in order.
+pub fn merge<T, I>(intervals: I) -> Vec<Interval<T>>
+where
+    T: Discrete,
+    I: IntoIterator<Item = Interval<T>>,
+{
+    let mut sorted = intervals.into_iter().collect::<Vec<Interval<T>>>();
+    sorted.sort_unstable_by_key(|interval| interval.start);
+    let mut merged: Vec<Interval<T>> = Vec::with_capacity(sorted.len());
+    for interval in sorted {
+        match merged.last_mut() {
+            Some(last) if last.touches(&interval) => last.end = max(last.end, interval.end),
+            _ => merged.push(interval),
+        }
+    }
+    merged
+}
+
+/// The parts of `within` that none of `intervals` cover, in order.
+pub fn uncovered<T, I>(intervals: I, within: Interval<T>) -> Vec<Interval<T>>
+where
+    T: Discrete,
+    I: IntoIterator<Item = Interval<T>>,
+{
+    merge(intervals).iter().fold(vec![within], |gaps, covered| {
+        gaps.iter()
+            .flat_map(|gap| gap.difference(covered))
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::iv;
+    use super::*;
+
+    #[test]
+    fn containment_and_intersection() {
+        assert_eq!(Interval::new(7, 3), None);
+        assert!(iv(2, 8).covers(&iv(3, 7)));
+        assert!(!iv(3, 7).covers(&iv(2, 8)));
+        assert!(iv(2, 8).contains(8));
+        assert_eq!(iv(5, 7).intersection(&iv(7, 9)), Some(Interval::point(7)));
+        assert_eq!(iv(2, 4).intersection(&iv(6, 8)), None);
+        assert_eq!(iv(2, 8).len(), 7);
+        assert_eq!(Interval::new(i64::MIN, i64::MAX).unwrap().len(), u64::MAX);
+        assert_eq!(iv(2, 8).to_string(), "2-8");
+    }
+
+    #[test]
+    fn union_and_difference() {
+        assert_eq!(iv(2, 3).union(&iv(4, 5)), Some(iv(2, 5)));
+        assert_eq!(iv(2, 3).union(&iv(5, 6)), None);
+        assert_eq!(iv(2, 8).difference(&iv(4, 5)), [iv(2, 3), iv(6, 8)]);
+        assert_eq!(iv(2, 8).difference(&iv(1, 5)), [iv(6, 8)]);
+        assert_eq!(iv(2, 8).difference(&iv(1, 9)), []);
+        assert_eq!(iv(2, 8).difference(&iv(9, 9)), [iv(2, 8)]);
+        let full = Interval::new(0u8, 255).unwrap();
+        assert_eq!(
+            full.difference(&Interval::point(0)),
+            [Interval::new(1, 255).unwrap()]
+        );
+    }
+
+    #[test]
+    fn merge_and_uncovered() {
+        let intervals = [iv(7, 9), iv(2, 3), iv(4, 5), iv(12, 12), iv(3, 4)];
+        assert_eq!(merge(intervals), [iv(2, 5), iv(7, 9), iv(12, 12)]);
+        assert_eq!(
+            uncovered(intervals, iv(1, 13)),
+            [iv(1, 1), iv(6, 6), iv(10, 11), iv(13, 13)]
+        );
+        assert_eq!(uncovered([], iv(1, 3)), [iv(1, 3)]);
+    }
+}
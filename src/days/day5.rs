@@ -1,101 +1,528 @@
-use std::{
-    error::Error,
-    fs::File,
-    io::{BufRead, BufReader, Lines},
-};
+use std::fmt::{self, Display};
+use std::io::BufRead;
+use std::str::FromStr;
 
-struct CargoStacks {
+use super::Solution;
+use crate::error::AocError;
+
+pub mod replay;
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+    type Input = (CargoStacks, Vec<Move>);
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Self::Input, AocError> {
+        let lines = reader.lines().collect::<Result<Vec<String>, _>>()?;
+        let drawing_len = lines
+            .iter()
+            .position(|line| line.is_empty())
+            .unwrap_or(lines.len());
+        let cargo = CargoStacks::parse_cargo(&lines[..drawing_len])?;
+        let moves = lines
+            .iter()
+            .enumerate()
+            .skip(drawing_len + 1)
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(line_idx, line)| {
+                Move::from_str(line)
+                    .map_err(|reason| AocError::parse(Day5::DAY, line_idx + 1, 1, line, reason))
+            })
+            .collect::<Result<Vec<Move>, AocError>>()?;
+        Ok((cargo, moves))
+    }
+
+    fn part1(&self, (cargo, moves): &Self::Input) -> Result<Self::Answer1, AocError> {
+        let mut cargo = cargo.clone();
+        cargo.move_cargo(moves, Crane::CrateMover9000)?;
+        Ok(cargo.top_crates_str())
+    }
+
+    fn part2(&self, (cargo, moves): &Self::Input) -> Result<Self::Answer2, AocError> {
+        let mut cargo = cargo.clone();
+        cargo.move_cargo(moves, Crane::CrateMover9001)?;
+        Ok(cargo.top_crates_str())
+    }
+}
+
+/// How many crates a crane lifts at once. Crates lifted together keep their order, so a crane
+/// that lifts one at a time reverses the moved crates.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Crane {
+    /// One crate at a time (part 1).
+    CrateMover9000,
+    /// Any number of crates at once (part 2).
+    CrateMover9001,
+    /// At most this many crates at once.
+    Limited(usize),
+}
+
+impl Display for Crane {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Crane::CrateMover9000 => write!(f, "CrateMover 9000"),
+            Crane::CrateMover9001 => write!(f, "CrateMover 9001"),
+            Crane::Limited(k) => write!(f, "crane lifting at most {} crates", k),
+        }
+    }
+}
+
+impl Crane {
+    /// Crates lifted at once, `None` if unlimited.
+    pub fn capacity(&self) -> Option<usize> {
+        match self {
+            Crane::CrateMover9000 => Some(1),
+            Crane::CrateMover9001 => None,
+            Crane::Limited(k) => Some(*k),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CargoStacks {
     stacks: Vec<Vec<char>>,
 }
 
 impl CargoStacks {
-    fn parse_cargo<R>(lines_iterator: &mut Lines<R>) -> Result<Self, Box<dyn Error>>
-    where
-        R: BufRead,
-    {
-        let (characters, stack_offsets) = lines_iterator
-            .by_ref()
-            .map_while(|line| match line.ok() {
-                Some(line) if !line.is_empty() => Some(line),
-                _ => None,
-            })
-            .fold(
-                (Vec::<(usize, char)>::new(), Vec::<usize>::new()),
-                |mut acc, line: String| {
-                    if line.contains(char::is_uppercase) {
-                        acc.0.extend(
-                            line.char_indices()
-                                .filter(|(_, letter)| letter.is_uppercase()),
-                        );
-                    } else {
-                        acc.1
-                            .extend(line.char_indices().filter_map(|elem| match elem {
-                                (idx, letter) if letter.is_numeric() => Some(idx),
-                                _ => None,
-                            }));
+    /// Crates of each stack, bottom first.
+    pub fn stacks(&self) -> &[Vec<char>] {
+        &self.stacks
+    }
+
+    pub fn parse_cargo(lines: &[String]) -> Result<Self, AocError> {
+        let (characters, stack_offsets) = lines.iter().enumerate().fold(
+            (
+                Vec::<(usize, usize, char)>::new(),
+                Vec::<(usize, usize)>::new(),
+            ),
+            |mut acc, (line_idx, line)| {
+                if line.contains(char::is_uppercase) {
+                    acc.0.extend(
+                        line.char_indices()
+                            .filter(|(_, letter)| letter.is_uppercase())
+                            .map(|(idx, letter)| (line_idx, idx, letter)),
+                    );
+                } else {
+                    // Each run of digits numbers one stack and spans the columns of its crates.
+                    for (idx, _) in line.char_indices().filter(|(_, c)| c.is_ascii_digit()) {
+                        match acc.1.last_mut() {
+                            Some((_, end)) if *end + 1 == idx => *end = idx,
+                            _ => acc.1.push((idx, idx)),
+                        }
                     }
-                    acc
-                },
-            );
+                }
+                acc
+            },
+        );
 
         let mut cargo = CargoStacks {
             stacks: vec![Vec::new(); stack_offsets.len()],
         };
 
-        for (idx, character) in characters.iter().rev() {
-            let stack_idx = stack_offsets.binary_search(idx).unwrap();
+        for (line_idx, idx, character) in characters.iter().rev() {
+            let stack_idx = stack_offsets.partition_point(|(_, end)| end < idx);
+            let above_stack = stack_offsets
+                .get(stack_idx)
+                .is_some_and(|(start, _)| start <= idx);
+            if !above_stack {
+                return Err(AocError::parse(
+                    Day5::DAY,
+                    line_idx + 1,
+                    idx + 1,
+                    &lines[*line_idx],
+                    "Crate is not above a numbered stack",
+                ));
+            }
             cargo.stacks[stack_idx].push(*character);
         }
 
         Ok(cargo)
     }
 
-    fn move_cargo<R>(&mut self, lines_iterator: &mut Lines<R>) -> Result<(), Box<dyn Error>>
+    /// Runs `moves` in order, stopping at the first one that cannot be carried out.
+    pub fn move_cargo(&mut self, moves: &[Move], crane: Crane) -> Result<(), AocError> {
+        self.move_cargo_traced(moves, crane, |_, _| {})
+    }
+
+    /// Like [`CargoStacks::move_cargo`], calling `after_move` with each move and the stacks it
+    /// left behind.
+    pub fn move_cargo_traced<F>(
+        &mut self,
+        moves: &[Move],
+        crane: Crane,
+        mut after_move: F,
+    ) -> Result<(), AocError>
     where
-        R: BufRead,
+        F: FnMut(&Move, &CargoStacks),
     {
-        for mov_op in lines_iterator.by_ref().filter_map(|line| {
-            let parse_results = line
-                .unwrap()
-                .split(' ')
-                .filter_map(|word_str| word_str.parse().ok())
-                .take(3)
-                .collect::<Vec<usize>>();
-            if parse_results.len() == 3 {
-                Some((parse_results[0], parse_results[1] - 1, parse_results[2] - 1))
-            } else {
-                None
+        for (idx, mv) in moves.iter().enumerate() {
+            self.apply(mv, crane).map_err(|e| {
+                AocError::no_solution(Day5::DAY, format!("move {} ({}): {}", idx + 1, mv, e))
+            })?;
+            after_move(mv, self);
+        }
+        Ok(())
+    }
+
+    /// Carries out one move, leaving the stacks untouched if it is not possible.
+    pub fn apply(&mut self, mv: &Move, crane: Crane) -> Result<(), MoveError> {
+        if crane.capacity() == Some(0) {
+            return Err(MoveError::IdleCrane);
+        }
+        for stack in [mv.from, mv.to] {
+            if stack == 0 || stack > self.stacks.len() {
+                return Err(MoveError::NoSuchStack {
+                    stack,
+                    stacks: self.stacks.len(),
+                });
             }
-        }) {
-            let range = (self.stacks[mov_op.1].len() - mov_op.0)..(self.stacks[mov_op.1].len());
-            let split_idx = (mov_op.1 + mov_op.2) / 2 + 1;
-            let (stacks_first, stacks_second) = self.stacks.split_at_mut(split_idx);
-            let stack_src;
-            let stack_dst;
-            if mov_op.1 < mov_op.2 {
-                stack_src = &mut (stacks_first[mov_op.1]);
-                stack_dst = &mut (stacks_second[mov_op.2 - split_idx]);
-            } else {
-                stack_src = &mut (stacks_second[mov_op.1 - split_idx]);
-                stack_dst = &mut (stacks_first[mov_op.2]);
+        }
+        if mv.from == mv.to {
+            return Err(MoveError::SameStack(mv.from));
+        }
+        let (from, to) = (mv.from - 1, mv.to - 1);
+        match self.stacks[from].len() {
+            0 if mv.count > 0 => return Err(MoveError::EmptyStack(mv.from)),
+            available if available < mv.count => {
+                return Err(MoveError::ShortStack {
+                    stack: mv.from,
+                    count: mv.count,
+                    available,
+                })
             }
-            //stack_dst.extend(stack_src.drain(range).rev()); // part1
-            stack_dst.extend(stack_src.drain(range)); // part2
+            _ => {}
+        }
+
+        let mut remaining = mv.count;
+        while remaining > 0 {
+            let lift = crane.capacity().map_or(remaining, |k| k.min(remaining));
+            let src_len = self.stacks[from].len();
+            let lifted = self.stacks[from].split_off(src_len - lift);
+            self.stacks[to].extend(lifted);
+            remaining -= lift;
         }
         Ok(())
     }
 
-    fn top_crates_str(&self) -> String {
-        self.stacks.iter().filter_map(|s| s.last()).cloned().collect()
+    pub fn top_crates_str(&self) -> String {
+        self.stacks
+            .iter()
+            .filter_map(|s| s.last())
+            .cloned()
+            .collect()
+    }
+}
+
+/// The puzzle's drawing: one row per crate height, then the stack numbers. Rows keep their
+/// trailing padding like the puzzle input, and [`CargoStacks::parse_cargo`] reads them back.
+/// With 10 or more stacks every column widens so each crate sits over its stack's number.
+impl Display for CargoStacks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.stacks.len().to_string().len() + 2;
+        let height = self.stacks.iter().map(Vec::len).max().unwrap_or(0);
+        for level in (0..height).rev() {
+            let row = self
+                .stacks
+                .iter()
+                .map(|stack| match stack.get(level) {
+                    Some(letter) => format!("{:^width$}", format!("[{}]", letter)),
+                    None => " ".repeat(width),
+                })
+                .collect::<Vec<String>>();
+            writeln!(f, "{}", row.join(" "))?;
+        }
+        let footer = (1..=self.stacks.len())
+            .map(|n| format!("{:^width$}", n))
+            .collect::<Vec<String>>();
+        write!(f, "{}", footer.join(" "))
+    }
+}
+
+/// One line of the rearrangement procedure; stacks are numbered from 1 as in the drawing.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Move {
+    pub count: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+impl FromStr for Move {
+    type Err = String;
+    /// Accepts exactly `move N from A to B`, with stack numbers from 1.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let words = value.split_whitespace().collect::<Vec<&str>>();
+        let ["move", count, "from", from, "to", to] = words[..] else {
+            return Err(format!("expected 'move N from A to B', found '{}'", value));
+        };
+        let number = |text: &str| {
+            usize::from_str(text).map_err(|e| format!("invalid number '{}': {}", text, e))
+        };
+        let (count, from, to) = (number(count)?, number(from)?, number(to)?);
+        if from == 0 || to == 0 {
+            return Err("stacks are numbered from 1".to_owned());
+        }
+        Ok(Move { count, from, to })
+    }
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "move {} from {} to {}", self.count, self.from, self.to)
     }
 }
 
-pub fn print_answer() {
-    let mut line_iterator = BufReader::new(File::open("data/input_day5").unwrap()).lines();
-    let mut cargo = CargoStacks::parse_cargo(&mut line_iterator).expect("Failed to parse cargo");
-    println!("parsed top crates: {}",cargo.top_crates_str());
-    cargo
-        .move_cargo(&mut line_iterator)
-        .expect("Failed to parse cargo");
-    println!("result top crates: {}",cargo.top_crates_str());
+/// Why a move cannot be carried out.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MoveError {
+    NoSuchStack {
+        stack: usize,
+        stacks: usize,
+    },
+    SameStack(usize),
+    EmptyStack(usize),
+    ShortStack {
+        stack: usize,
+        count: usize,
+        available: usize,
+    },
+    IdleCrane,
+}
+
+impl Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveError::NoSuchStack { stack, stacks } => {
+                write!(f, "no stack {}, there are {} stacks", stack, stacks)
+            }
+            MoveError::SameStack(stack) => {
+                write!(f, "cannot move crates from stack {} onto itself", stack)
+            }
+            MoveError::EmptyStack(stack) => write!(f, "stack {} is empty", stack),
+            MoveError::ShortStack {
+                stack,
+                count,
+                available,
+            } => write!(
+                f,
+                "cannot move {} crates from stack {}, which holds {}",
+                count, stack, available
+            ),
+            MoveError::IdleCrane => write!(f, "a crane must lift at least one crate"),
+        }
+    }
+}
+
+impl std::error::Error for MoveError {}
+
+pub fn print_answer() -> Result<(), AocError> {
+    super::print_solution(&Day5)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub(super) const EXAMPLE: &str = "    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2
+";
+
+    #[test]
+    fn example_parse_cargo() {
+        let (cargo, moves) = Day5.parse(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(
+            cargo.stacks(),
+            &[vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']]
+        );
+        assert_eq!(
+            moves,
+            [
+                Move {
+                    count: 1,
+                    from: 2,
+                    to: 1
+                },
+                Move {
+                    count: 3,
+                    from: 1,
+                    to: 3
+                },
+                Move {
+                    count: 2,
+                    from: 2,
+                    to: 1
+                },
+                Move {
+                    count: 1,
+                    from: 1,
+                    to: 2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn drawing_round_trips() {
+        let (mut cargo, moves) = Day5.parse(EXAMPLE.as_bytes()).unwrap();
+        let drawing = cargo.to_string();
+        assert_eq!(drawing, EXAMPLE[..EXAMPLE.find("\n\n").unwrap()]);
+
+        let mut drawings = Vec::new();
+        cargo
+            .move_cargo_traced(&moves, Crane::CrateMover9000, |mv, cargo| {
+                drawings.push(format!("{}\n{}", mv, cargo))
+            })
+            .unwrap();
+        assert_eq!(drawings.len(), moves.len());
+        assert_eq!(
+            drawings[1],
+            "move 3 from 1 to 3
+        [Z]
+        [N]
+    [C] [D]
+    [M] [P]
+ 1   2   3 "
+        );
+        let lines = cargo
+            .to_string()
+            .lines()
+            .map(str::to_owned)
+            .collect::<Vec<String>>();
+        assert_eq!(CargoStacks::parse_cargo(&lines).unwrap(), cargo);
+    }
+
+    #[test]
+    fn wide_drawing_round_trips() {
+        for count in [12, 120] {
+            let cargo = CargoStacks {
+                stacks: (0..count)
+                    .map(|idx| vec![char::from(b'A' + (idx % 26) as u8); idx % 3])
+                    .collect(),
+            };
+            let drawing = cargo.to_string();
+            let lines = drawing.lines().map(str::to_owned).collect::<Vec<String>>();
+            assert_eq!(
+                CargoStacks::parse_cargo(&lines).unwrap(),
+                cargo,
+                "{}",
+                drawing
+            );
+        }
+        let mut stacks = vec![Vec::new(); 10];
+        stacks[0].push('A');
+        stacks[9].push('J');
+        let cargo = CargoStacks { stacks };
+        assert_eq!(
+            cargo.to_string(),
+            "[A]                                          [J] \n 1    2    3    4    5    6    7    8    9    10 "
+        );
+    }
+
+    #[test]
+    fn example_move_cargo() {
+        let input = Day5.parse(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(Day5.part1(&input).unwrap(), "CMZ");
+        assert_eq!(Day5.part2(&input).unwrap(), "MCD");
+    }
+
+    #[test]
+    fn limited_crane_lifts_in_batches() {
+        let (cargo, _) = Day5.parse(EXAMPLE.as_bytes()).unwrap();
+        let moves = ["move 3 from 2 to 3".parse::<Move>().unwrap()];
+        let lifted = |crane| {
+            let mut cargo = cargo.clone();
+            cargo.move_cargo(&moves, crane).unwrap();
+            cargo.stacks()[2].clone()
+        };
+        assert_eq!(lifted(Crane::CrateMover9000), ['P', 'D', 'C', 'M']);
+        assert_eq!(lifted(Crane::CrateMover9001), ['P', 'M', 'C', 'D']);
+        assert_eq!(lifted(Crane::Limited(2)), ['P', 'C', 'D', 'M']);
+        assert_eq!(lifted(Crane::Limited(3)), lifted(Crane::CrateMover9001));
+        let mut cargo = cargo.clone();
+        assert!(cargo.move_cargo(&moves, Crane::Limited(0)).is_err());
+    }
+
+    #[test]
+    fn strict_move_parsing() {
+        assert_eq!(
+            Move::from_str("move 12 from 3 to 1"),
+            Ok(Move {
+                count: 12,
+                from: 3,
+                to: 1
+            })
+        );
+        assert_eq!(
+            Move::from_str("move 1 from 2 to 1").unwrap().to_string(),
+            "move 1 from 2 to 1"
+        );
+        for bad in [
+            "move 1 from 2",
+            "move 1 from 2 to 3 now",
+            "shift 1 from 2 to 3",
+            "move -1 from 2 to 3",
+            "move 1 from 0 to 3",
+        ] {
+            assert!(Move::from_str(bad).is_err(), "{}", bad);
+        }
+        let input = EXAMPLE.replace("move 2 from 2 to 1", "move 2 from two to 1");
+        assert!(matches!(
+            Day5.parse(input.as_bytes()),
+            Err(AocError::Parse { line: 8, .. })
+        ));
+    }
+
+    #[test]
+    fn invalid_moves_are_typed_errors() {
+        let (cargo, _) = Day5.parse(EXAMPLE.as_bytes()).unwrap();
+        let apply = |text: &str| {
+            let mut cargo = cargo.clone();
+            let result = cargo.apply(&text.parse().unwrap(), Crane::CrateMover9001);
+            (result, cargo)
+        };
+        assert_eq!(
+            apply("move 3 from 3 to 1").0,
+            Err(MoveError::ShortStack {
+                stack: 3,
+                count: 3,
+                available: 1
+            })
+        );
+        assert_eq!(
+            apply("move 1 from 4 to 1").0,
+            Err(MoveError::NoSuchStack {
+                stack: 4,
+                stacks: 3
+            })
+        );
+        assert_eq!(apply("move 1 from 2 to 2").0, Err(MoveError::SameStack(2)));
+        let (result, mut emptied) = apply("move 1 from 3 to 1");
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            emptied.apply(
+                &"move 1 from 3 to 2".parse().unwrap(),
+                Crane::CrateMover9000
+            ),
+            Err(MoveError::EmptyStack(3))
+        );
+        assert_eq!(apply("move 3 from 3 to 1").1, cargo);
+
+        let mut cargo = cargo.clone();
+        assert!(matches!(
+            cargo.move_cargo(
+                &["move 3 from 3 to 1".parse().unwrap()],
+                Crane::CrateMover9001
+            ),
+            Err(AocError::NoSolution { .. })
+        ));
+    }
 }
@@ -0,0 +1,192 @@
+//! Stepping through a rearrangement procedure in both directions, for teaching and debugging.
+
+use super::{CargoStacks, Crane, Move, MoveError};
+
+/// What an applied move took off its source stack, bottom first, so it can be put back whatever
+/// order the crane left the crates in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Lift {
+    from: usize,
+    to: usize,
+    crates: Vec<char>,
+}
+
+/// A procedure replayed one move at a time, with every applied move logged so it can be undone.
+#[derive(Clone, Debug)]
+pub struct Simulator {
+    cargo: CargoStacks,
+    moves: Vec<Move>,
+    crane: Crane,
+    log: Vec<Lift>,
+}
+
+impl Simulator {
+    pub fn new(cargo: CargoStacks, moves: Vec<Move>, crane: Crane) -> Self {
+        Simulator {
+            cargo,
+            moves,
+            crane,
+            log: Vec::new(),
+        }
+    }
+
+    /// The stacks after the moves applied so far.
+    pub fn cargo(&self) -> &CargoStacks {
+        &self.cargo
+    }
+
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    /// How many moves have been applied; 0 is the starting drawing.
+    pub fn step(&self) -> usize {
+        self.log.len()
+    }
+
+    /// Applies the next move; `false` if every move has already been applied.
+    pub fn forward(&mut self) -> Result<bool, MoveError> {
+        let Some(mv) = self.moves.get(self.log.len()) else {
+            return Ok(false);
+        };
+        // Read before applying; if the stack does not exist `apply` reports it.
+        let crates = mv
+            .from
+            .checked_sub(1)
+            .and_then(|from| self.cargo.stacks.get(from))
+            .map(|stack| stack[stack.len().saturating_sub(mv.count)..].to_vec());
+        self.cargo.apply(mv, self.crane)?;
+        self.log.push(Lift {
+            from: mv.from - 1,
+            to: mv.to - 1,
+            crates: crates.expect("apply checks the source stack"),
+        });
+        Ok(true)
+    }
+
+    /// Undoes the last applied move; `false` if back at the starting drawing.
+    pub fn back(&mut self) -> bool {
+        let Some(lift) = self.log.pop() else {
+            return false;
+        };
+        let to = &mut self.cargo.stacks[lift.to];
+        to.truncate(to.len() - lift.crates.len());
+        self.cargo.stacks[lift.from].extend(lift.crates);
+        true
+    }
+
+    /// Moves forward or back until `step` moves have been applied; `false`, without moving, if
+    /// the procedure has fewer moves.
+    pub fn jump(&mut self, step: usize) -> Result<bool, MoveError> {
+        if step > self.moves.len() {
+            return Ok(false);
+        }
+        while self.step() > step {
+            self.back();
+        }
+        while self.step() < step {
+            self.forward()?;
+        }
+        Ok(true)
+    }
+
+    /// The first step after which crate `letter` is on top of stack `stack` (numbered from 1),
+    /// counting the starting drawing as step 0.
+    pub fn reached_top(&self, letter: char, stack: usize) -> Result<Option<usize>, MoveError> {
+        let on_top = |sim: &Simulator| {
+            stack
+                .checked_sub(1)
+                .and_then(|idx| sim.cargo.stacks.get(idx))
+                .and_then(|crates| crates.last())
+                == Some(&letter)
+        };
+        let mut sim = self.clone();
+        sim.jump(0)?;
+        loop {
+            if on_top(&sim) {
+                return Ok(Some(sim.step()));
+            }
+            if !sim.forward()? {
+                return Ok(None);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::EXAMPLE;
+    use super::super::Day5;
+    use super::*;
+    use crate::days::Solution;
+
+    fn simulator(crane: Crane) -> Simulator {
+        let (cargo, moves) = Day5.parse(EXAMPLE.as_bytes()).unwrap();
+        Simulator::new(cargo, moves, crane)
+    }
+
+    #[test]
+    fn step_forward_and_back() {
+        for crane in [
+            Crane::CrateMover9000,
+            Crane::CrateMover9001,
+            Crane::Limited(2),
+        ] {
+            let mut sim = simulator(crane);
+            let initial = sim.cargo().clone();
+            let mut states = vec![initial.clone()];
+            while sim.forward().unwrap() {
+                states.push(sim.cargo().clone());
+            }
+            assert_eq!(sim.step(), 4);
+            let mut expected = initial.clone();
+            expected.move_cargo(sim.moves(), crane).unwrap();
+            assert_eq!(sim.cargo(), &expected);
+
+            while sim.back() {
+                assert_eq!(sim.cargo(), &states[sim.step()], "{}", crane);
+            }
+            assert_eq!(sim.step(), 0);
+            assert!(!sim.back());
+            assert_eq!(sim.cargo(), &initial);
+        }
+    }
+
+    #[test]
+    fn jump_to_step() {
+        let mut sim = simulator(Crane::CrateMover9000);
+        assert_eq!(sim.jump(4), Ok(true));
+        assert_eq!(sim.cargo().top_crates_str(), "CMZ");
+        assert_eq!(sim.jump(1), Ok(true));
+        assert_eq!(sim.cargo().stacks()[0], ['Z', 'N', 'D']);
+        assert_eq!(sim.jump(5), Ok(false));
+        assert_eq!(sim.step(), 1);
+    }
+
+    #[test]
+    fn step_crate_reached_top() {
+        let mut sim = simulator(Crane::CrateMover9000);
+        sim.jump(3).unwrap();
+        assert_eq!(sim.reached_top('N', 1), Ok(Some(0)));
+        assert_eq!(sim.reached_top('D', 1), Ok(Some(1)));
+        assert_eq!(sim.reached_top('Z', 3), Ok(Some(2)));
+        assert_eq!(sim.reached_top('M', 2), Ok(Some(4)));
+        assert_eq!(sim.reached_top('P', 2), Ok(None));
+        assert_eq!(sim.reached_top('P', 9), Ok(None));
+        assert_eq!(sim.step(), 3);
+    }
+
+    #[test]
+    fn invalid_move_stops_replay() {
+        let (cargo, _) = Day5.parse(EXAMPLE.as_bytes()).unwrap();
+        let moves = ["move 1 from 3 to 1", "move 1 from 3 to 2"]
+            .iter()
+            .map(|text| text.parse().unwrap())
+            .collect();
+        let mut sim = Simulator::new(cargo, moves, Crane::CrateMover9001);
+        assert_eq!(sim.forward(), Ok(true));
+        assert_eq!(sim.forward(), Err(MoveError::EmptyStack(3)));
+        assert_eq!(sim.step(), 1);
+        assert_eq!(sim.jump(2), Err(MoveError::EmptyStack(3)));
+    }
+}
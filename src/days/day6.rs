@@ -1,43 +1,105 @@
-use std::{
-    fs::File,
-    io::{BufReader, Read},
-};
+use std::io::BufRead;
 
-fn check_disjoint(pattern: &str) -> bool {
-    match pattern.as_bytes() {
-        [a,..]  => !pattern[1..].as_bytes().contains(a) && check_disjoint(&pattern[1..]),
-        _ => true
+use super::Solution;
+use crate::error::AocError;
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+    type Input = String;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<R: BufRead>(&self, mut reader: R) -> Result<Self::Input, AocError> {
+        let mut message = "".to_string();
+        reader.read_to_string(&mut message)?;
+        message.truncate(message.trim_end_matches(['\n', '\r']).len());
+        Ok(message)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Answer1, AocError> {
+        const START_PATTERN_LEN: usize = 4;
+        find_marker(input, START_PATTERN_LEN)
+            .ok_or_else(|| AocError::no_solution(Self::DAY, "no start-of-packet marker"))
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Self::Answer2, AocError> {
+        const MESSAGE_PATTERN_LEN: usize = 14;
+        find_marker(input, MESSAGE_PATTERN_LEN)
+            .ok_or_else(|| AocError::no_solution(Self::DAY, "no start-of-message marker"))
     }
 }
 
-pub fn print_answer() {
-    let mut buf_read = BufReader::new(File::open("data/input_day6").unwrap());
-    let mut message = "".to_string();
-    buf_read.read_to_string(&mut message).expect("Unable to read the file");
-
-    // part 1
-    const START_PATTERN_LEN: usize = 4;
-    if message.len() < START_PATTERN_LEN {
-        println!("String too short {}", message.len());
-        return;
-    }
-    for offset in 0..(message.len() - START_PATTERN_LEN) {
-        if check_disjoint(&message[offset..(offset + START_PATTERN_LEN)]) {
-            println!("Offset packet: {}", offset + START_PATTERN_LEN);
-            break;
-        }
+pub fn check_disjoint(pattern: &[u8]) -> bool {
+    match pattern {
+        [a, rest @ ..] => !rest.contains(a) && check_disjoint(rest),
+        _ => true,
     }
+}
 
-    // part 2
-    const MESSAGE_PATTERN_LEN: usize = 14;
-    if message.len() < MESSAGE_PATTERN_LEN {
-        println!("String too short for message {}", message.len());
-        return;
+/// Bytes read up to the end of the first `pattern_len` distinct bytes.
+pub fn find_marker(message: &str, pattern_len: usize) -> Option<usize> {
+    if pattern_len == 0 {
+        return None;
     }
-    for offset in 0..(message.len() - MESSAGE_PATTERN_LEN) {
-        if check_disjoint(&message[offset..(offset + MESSAGE_PATTERN_LEN)]) {
-            println!("Offset message: {}", offset + MESSAGE_PATTERN_LEN);
-            break;
+    message
+        .as_bytes()
+        .windows(pattern_len)
+        .position(check_disjoint)
+        .map(|offset| offset + pattern_len)
+}
+
+pub fn print_answer() -> Result<(), AocError> {
+    super::print_solution(&Day6)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLES: [(&str, usize, usize); 5] = [
+        ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+        ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+        ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+        ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+        ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+    ];
+
+    #[test]
+    fn example_markers() {
+        for (message, packet, start) in EXAMPLES {
+            assert_eq!(find_marker(message, 4), Some(packet), "{}", message);
+            assert_eq!(find_marker(message, 14), Some(start), "{}", message);
         }
     }
+
+    #[test]
+    fn short_message_has_no_marker() {
+        assert_eq!(find_marker("abc", 4), None);
+        assert_eq!(find_marker("aaaaaaa", 4), None);
+    }
+
+    #[test]
+    fn marker_at_end_of_message() {
+        assert_eq!(find_marker("abcd", 4), Some(4));
+        assert_eq!(find_marker("aabcd", 4), Some(5));
+        let input = Day6.parse("abcdefghijklmn\n".as_bytes()).unwrap();
+        assert_eq!(input, "abcdefghijklmn");
+        assert_eq!(Day6.part2(&input).unwrap(), 14);
+    }
+
+    #[test]
+    fn non_ascii_message_does_not_panic() {
+        let input = Day6.parse("ééééééé\n".as_bytes()).unwrap();
+        assert!(Day6.part1(&input).is_err());
+        assert_eq!(find_marker("éa", 3), Some(3));
+    }
+
+    #[test]
+    fn disjoint_patterns() {
+        assert!(check_disjoint(b"abcd"));
+        assert!(!check_disjoint(b"abca"));
+        assert!(check_disjoint(b""));
+    }
 }
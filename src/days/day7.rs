@@ -1,12 +1,42 @@
 use std::{
-    fs::File,
     io::BufRead,
-    io::BufReader,
     path::{Path, PathBuf},
 };
 
+use super::Solution;
+use crate::error::AocError;
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+    type Input = DirTree;
+    type Answer1 = isize;
+    type Answer2 = isize;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Self::Input, AocError> {
+        parse_dirs(reader)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Answer1, AocError> {
+        let dir_sizes = input.root.get_sizes();
+        Ok(dirs_below_limit_size(100_000, &dir_sizes.1))
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Self::Answer2, AocError> {
+        let dir_sizes = input.root.get_sizes();
+        let space_min_to_be_freed = 30_000_000 - (70_000_000 - dir_sizes.0);
+        dir_size_to_be_removed(space_min_to_be_freed, &dir_sizes.1).ok_or_else(|| {
+            AocError::no_solution(
+                Self::DAY,
+                format!("no directory frees {} bytes", space_min_to_be_freed),
+            )
+        })
+    }
+}
+
 #[derive(Debug)]
-enum DirElem {
+pub enum DirElem {
     FileNode {
         name: String,
         size: isize,
@@ -18,26 +48,25 @@ enum DirElem {
 }
 
 impl DirElem {
-    fn get_name(&self) -> &str {
+    pub fn get_name(&self) -> &str {
         match &self {
             DirElem::DirNode { name, .. } => name,
             DirElem::FileNode { name, .. } => name,
         }
     }
 
-    fn get_sizes(&self) -> (isize, Vec<(String, isize)>) {
+    pub fn get_sizes(&self) -> (isize, Vec<(String, isize)>) {
         match self {
             DirElem::FileNode { size, .. } => (*size, Vec::new()),
             DirElem::DirNode { name, contents } => {
-                let (total, mut subdirs) = contents.iter().fold(
-                    (0, Vec::new()),
-                    |(total_size, mut dirs), node| match node.get_sizes() {
-                        (node_size, node_dirs) => {
+                let (total, mut subdirs) =
+                    contents
+                        .iter()
+                        .fold((0, Vec::new()), |(total_size, mut dirs), node| {
+                            let (node_size, node_dirs) = node.get_sizes();
                             dirs.extend(node_dirs);
                             (total_size + node_size, dirs)
-                        }
-                    },
-                );
+                        });
                 subdirs.push((name.to_owned(), total));
                 (total, subdirs)
             }
@@ -46,12 +75,12 @@ impl DirElem {
 }
 
 #[derive(Debug)]
-struct DirTree {
+pub struct DirTree {
     root: DirElem,
 }
 
 impl DirTree {
-    fn new() -> Self {
+    pub fn new() -> Self {
         Self {
             root: DirElem::DirNode {
                 name: "/".to_string(),
@@ -60,13 +89,17 @@ impl DirTree {
         }
     }
 
-    fn check_name(test_name: &str, elems: &Vec<DirElem>) -> bool {
+    pub fn root(&self) -> &DirElem {
+        &self.root
+    }
+
+    fn check_name(test_name: &str, elems: &[DirElem]) -> bool {
         elems.iter().all(|node| node.get_name() != test_name)
     }
 
     fn add_dir_elem(new_elem: DirElem, contents: &mut Vec<DirElem>) -> Result<(), &'static str> {
         if !DirTree::check_name(new_elem.get_name(), contents) {
-            return Err("Error adding DirElem - already exists");
+            return Err("Duplicate directory entry");
         }
         contents.push(new_elem);
         Ok(())
@@ -76,9 +109,9 @@ impl DirTree {
         path.as_ref()
             .iter()
             .try_fold(&mut self.root, |curr_dir, next_dir| match curr_dir {
-                _ if curr_dir.get_name() == next_dir.to_str().unwrap() => Some(curr_dir),
+                _ if next_dir == curr_dir.get_name() => Some(curr_dir),
                 DirElem::DirNode { contents, .. } => {
-                    contents.into_iter().find(|x| x.get_name() == next_dir)
+                    contents.iter_mut().find(|x| x.get_name() == next_dir)
                 }
                 _ => None,
             })
@@ -93,56 +126,84 @@ impl DirTree {
             return DirTree::add_dir_elem(new_elem, contents);
         }
 
-        Err("Error adding element to path")
+        Err("Listing inside an unknown directory")
+    }
+}
+
+impl Default for DirTree {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
-fn parse_dirs<B>(input_reader: B) -> DirTree
+pub fn parse_dirs<B>(input_reader: B) -> Result<DirTree, AocError>
 where
     B: BufRead,
 {
-    input_reader
+    Ok(input_reader
         .lines()
-        .fold(
+        .enumerate()
+        .try_fold(
             (DirTree::new(), PathBuf::from("/")),
-            |(mut tree, path), line| {
-                let parsed_line = line.unwrap_or_default();
-                match parsed_line.trim_start_matches('$').trim().split_once(' ') {
-                    Some(("cd", "/")) => (tree, PathBuf::from("/")),
-                    Some(("cd", "..")) => {
-                        (tree, path.parent().unwrap_or(Path::new("/")).to_owned())
-                    }
-                    Some(("cd", dir_name)) => (tree, path.join(dir_name).to_owned()),
-                    Some(("dir", dir_name)) => {
-                        tree.add_dir_elem_path(
-                            DirElem::DirNode {
-                                name: dir_name.to_owned(),
-                                contents: Vec::new(),
-                            },
-                            &path,
-                        )
-                        .ok();
-                        (tree, path)
-                    }
+            |(mut tree, path), (line_idx, line)| -> Result<(DirTree, PathBuf), AocError> {
+                let parsed_line = line?;
+                let parse_err = |field: &str, reason| {
+                    let column = field.as_ptr() as usize - parsed_line.as_ptr() as usize + 1;
+                    AocError::parse(Day7::DAY, line_idx + 1, column, &parsed_line, reason)
+                };
+                let trimmed = parsed_line.trim();
+                if trimmed.is_empty() {
+                    return Ok((tree, path));
+                }
+                if let Some(command) = trimmed.strip_prefix('$') {
+                    let command = command.trim_start();
+                    return Ok(match command.split_once(' ') {
+                        Some(("cd", "/")) => (tree, PathBuf::from("/")),
+                        Some(("cd", "..")) => {
+                            (tree, path.parent().unwrap_or(Path::new("/")).to_owned())
+                        }
+                        Some(("cd", dir_name)) => (tree, path.join(dir_name)),
+                        None if command == "ls" => (tree, path),
+                        _ => return Err(parse_err(command, "Expected 'cd <dir>' or 'ls'")),
+                    });
+                }
+
+                let (new_elem, name) = match trimmed.split_once(' ') {
+                    Some(("dir", dir_name)) => (
+                        DirElem::DirNode {
+                            name: dir_name.to_owned(),
+                            contents: Vec::new(),
+                        },
+                        dir_name,
+                    ),
                     Some((file_size, file_name)) => {
-                        tree.add_dir_elem_path(
+                        let size = file_size
+                            .parse()
+                            .map_err(|_| parse_err(file_size, "Invalid file size"))?;
+                        (
                             DirElem::FileNode {
                                 name: file_name.to_owned(),
-                                size: file_size.parse().unwrap(),
+                                size,
                             },
-                            &path,
+                            file_name,
                         )
-                        .ok();
-                        (tree, path)
                     }
-                    _ => (tree, path),
-                }
+                    None => {
+                        return Err(parse_err(
+                            trimmed,
+                            "Expected '$ <command>', 'dir <name>' or '<size> <name>'",
+                        ))
+                    }
+                };
+                tree.add_dir_elem_path(new_elem, &path)
+                    .map_err(|reason| parse_err(name, reason))?;
+                Ok((tree, path))
             },
-        )
-        .0
+        )?
+        .0)
 }
 
-fn dirs_below_limit_size(size_limit: isize, dir_sizes: &Vec<(String, isize)>) -> isize {
+pub fn dirs_below_limit_size(size_limit: isize, dir_sizes: &[(String, isize)]) -> isize {
     dir_sizes
         .iter()
         .filter_map(|(_, dir_size)| {
@@ -155,7 +216,10 @@ fn dirs_below_limit_size(size_limit: isize, dir_sizes: &Vec<(String, isize)>) ->
         .sum()
 }
 
-fn dir_size_to_be_removed(space_required: isize, dir_sizes: &Vec<(String, isize)>) -> isize {
+pub fn dir_size_to_be_removed(
+    space_required: isize,
+    dir_sizes: &[(String, isize)],
+) -> Option<isize> {
     dir_sizes
         .iter()
         .filter_map(|(_, dir_size)| {
@@ -166,23 +230,103 @@ fn dir_size_to_be_removed(space_required: isize, dir_sizes: &Vec<(String, isize)
             }
         })
         .min()
-        .unwrap()
-        .clone()
+        .copied()
 }
 
-pub fn print_answer() {
-    let buf_read = BufReader::new(File::open("data/input_day7").unwrap());
-    let file_tree = parse_dirs(buf_read);
-    //println!("Parsed tree: {:?}", file_tree);
-    let dir_sizes = file_tree.root.get_sizes();
-    let limit = 100_000;
-    let size = dirs_below_limit_size(limit, &dir_sizes.1);
-    println!("Size of dirs not larger than {} is: {}", limit, size);
-    // part 2
-    let space_min_to_be_freed = 30_000_000 - (70_000_000 - dir_sizes.0);
-    let remove_size = dir_size_to_be_removed(space_min_to_be_freed, &dir_sizes.1);
-    println!(
-        "Size of dir closest to required {} is: {}",
-        space_min_to_be_freed, remove_size
-    );
+pub fn print_answer() -> Result<(), AocError> {
+    super::print_solution(&Day7)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k
+";
+
+    #[test]
+    fn example_dir_sizes() {
+        let tree = parse_dirs(EXAMPLE.as_bytes()).unwrap();
+        let (total, mut dirs) = tree.root().get_sizes();
+        dirs.sort();
+        assert_eq!(total, 48381165);
+        assert_eq!(
+            dirs,
+            [
+                ("/".to_owned(), 48381165),
+                ("a".to_owned(), 94853),
+                ("d".to_owned(), 24933642),
+                ("e".to_owned(), 584),
+            ]
+        );
+    }
+
+    #[test]
+    fn example_answers() {
+        let tree = Day7.parse(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(Day7.part1(&tree).unwrap(), 95437);
+        assert_eq!(Day7.part2(&tree).unwrap(), 24933642);
+    }
+
+    #[test]
+    fn unknown_directory_and_duplicates_are_errors() {
+        assert!(matches!(
+            parse_dirs("$ cd /\n$ cd nowhere\n$ ls\n5000 b\n".as_bytes()),
+            Err(AocError::Parse {
+                line: 4,
+                column: 6,
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse_dirs("$ cd /\n$ ls\ndir a\n5000 a\n".as_bytes()),
+            Err(AocError::Parse {
+                line: 4,
+                column: 6,
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse_dirs("$ cd /\n$ pwd\n".as_bytes()),
+            Err(AocError::Parse {
+                line: 2,
+                column: 3,
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse_dirs("$ cd /\n$ ls\nb.txt\n".as_bytes()),
+            Err(AocError::Parse { line: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn invalid_file_size_reports_line() {
+        match parse_dirs("$ cd /\n$ ls\n12a b.txt\n".as_bytes()) {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (3, 1)),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
 }
@@ -1,19 +1,49 @@
-use std::{
-    borrow::Borrow, cmp::max, collections::HashSet, fs::File, io::BufRead, io::BufReader, u32,
-};
+use std::{borrow::Borrow, cmp::max, collections::HashSet, io::BufRead};
+
+use super::Solution;
+use crate::error::AocError;
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+    type Input = Vec<Vec<Tree>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Self::Input, AocError> {
+        parse_lines(reader)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Answer1, AocError> {
+        Ok(count_visible_trees(input))
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Self::Answer2, AocError> {
+        Ok(highest_scenic_score(input))
+    }
+}
 
 #[derive(Clone, Copy, Debug)]
-struct Tree {
+pub struct Tree {
     id: usize,
     height: u32,
 }
 
+impl Tree {
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Height as drawn in the input, `0..=9`.
+    pub fn height(&self) -> u32 {
+        self.height - 1
+    }
+}
+
 impl From<(usize, u32)> for Tree {
     fn from((id, height): (usize, u32)) -> Self {
-        Tree {
-            id: id,
-            height: height,
-        }
+        Tree { id, height }
     }
 }
 
@@ -34,26 +64,51 @@ fn check_line(tree_line: &[Tree], hidden_set: &mut HashSet<usize>) {
     tree_line.iter().rev().fold((0u32, set_updated), check_tree);
 }
 
-fn parse_lines<B>(input_reader: B) -> Vec<Vec<Tree>>
+pub fn parse_lines<B>(input_reader: B) -> Result<Vec<Vec<Tree>>, AocError>
 where
     B: BufRead,
 {
     let mut tree_ids = 1..;
-    input_reader
+    let mut line_width = None;
+    let trees = input_reader
         .lines()
-        .filter_map(Result::ok)
-        .map(|l| {
-            tree_ids
+        .enumerate()
+        .map(|(line_idx, l)| {
+            let l = l?;
+            let parse_err = |column, reason: String| {
+                AocError::parse(Day8::DAY, line_idx + 1, column, &l, reason)
+            };
+            let heights = l
+                .chars()
+                .enumerate()
+                .map(|(idx, c)| match c.to_digit(10) {
+                    Some(height) => Ok(height + 1),
+                    None => Err(parse_err(idx + 1, "Tree height must be a digit".to_owned())),
+                })
+                .collect::<Result<Vec<u32>, AocError>>()?;
+            match *line_width.get_or_insert(heights.len()) {
+                0 => return Err(parse_err(1, "Empty tree line".to_owned())),
+                width if width != heights.len() => {
+                    return Err(parse_err(1, format!("Expected {} trees per line", width)))
+                }
+                _ => (),
+            }
+            Ok(tree_ids
                 .by_ref()
-                .zip(l.chars().map(|c| c.to_digit(10).unwrap() + 1))
+                .zip(heights)
                 .map(Tree::from)
-                .collect::<Vec<Tree>>()
+                .collect::<Vec<Tree>>())
         })
-        .collect()
+        .collect::<Result<Vec<Vec<Tree>>, AocError>>()?;
+
+    if trees.is_empty() {
+        return Err(AocError::no_solution(Day8::DAY, "input contains no trees"));
+    }
+    Ok(trees)
 }
 
-fn count_visible_trees(trees: &Vec<Vec<Tree>>) -> usize {
-    let num_cols = trees.first().unwrap().len();
+pub fn count_visible_trees(trees: &[Vec<Tree>]) -> usize {
+    let num_cols = trees.first().map_or(0, Vec::len);
     let num_trees = num_cols * trees.len();
     let mut hidden_trees: HashSet<usize> = HashSet::from_iter(trees.iter().flatten().map(|t| t.id));
 
@@ -73,11 +128,10 @@ fn count_visible_trees(trees: &Vec<Vec<Tree>>) -> usize {
         check_line(&col, &mut hidden_trees)
     }
 
-    println!("Id set size after checks: {}", hidden_trees.len());
     num_trees - hidden_trees.len()
 }
 
-fn scenic_score<I>(trees: I, height: u32) -> usize
+pub fn scenic_score<I>(trees: I, height: u32) -> usize
 where
     I: IntoIterator,
     I::Item: Borrow<Tree>,
@@ -90,12 +144,13 @@ where
             } else {
                 (score + 1, tree.borrow().height)
             }
-        }).0
+        })
+        .0
 }
 
-fn highest_scenic_score(trees: &Vec<Vec<Tree>>) -> usize {
+pub fn highest_scenic_score(trees: &[Vec<Tree>]) -> usize {
     let mut trees_cols: Vec<Vec<Tree>> = Vec::new();
-    let num_cols = trees.first().unwrap().len();
+    let num_cols = trees.first().map_or(0, Vec::len);
     for k in 0..num_cols {
         trees_cols.push(
             trees
@@ -122,9 +177,38 @@ fn highest_scenic_score(trees: &Vec<Vec<Tree>>) -> usize {
     max_score
 }
 
-pub fn print_answer() {
-    let buf_read = BufReader::new(File::open("data/input_day8").unwrap());
-    let trees = parse_lines(buf_read);
-    println!("Visible trees: {}", count_visible_trees(&trees));
-    println!("Max scenic score: {}", highest_scenic_score(&trees));
+pub fn print_answer() -> Result<(), AocError> {
+    super::print_solution(&Day8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "30373
+25512
+65332
+33549
+35390
+";
+
+    #[test]
+    fn example_visible_trees() {
+        let trees = parse_lines(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(count_visible_trees(&trees), 21);
+    }
+
+    #[test]
+    fn example_scenic_score() {
+        let trees = parse_lines(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(highest_scenic_score(&trees), 8);
+    }
+
+    #[test]
+    fn ragged_grid_is_rejected() {
+        match parse_lines("303\n25\n".as_bytes()) {
+            Err(AocError::Parse { line, .. }) => assert_eq!(line, 2),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
 }
@@ -0,0 +1,173 @@
+// mod day9
+
+use std::{collections::HashSet, io::BufRead};
+
+use super::Solution;
+use crate::error::AocError;
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+    type Input = Vec<Motion>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Self::Input, AocError> {
+        read_motions(reader)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Answer1, AocError> {
+        Ok(tail_positions(input, 2))
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Self::Answer2, AocError> {
+        Ok(tail_positions(input, 10))
+    }
+}
+
+pub fn print_answer() -> Result<(), AocError> {
+    super::print_solution(&Day9)
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    fn delta(&self) -> (i32, i32) {
+        match self {
+            Direction::Up => (0, 1),
+            Direction::Down => (0, -1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+        }
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = &'static str;
+    fn try_from(letter: char) -> Result<Self, Self::Error> {
+        match letter {
+            'U' => Ok(Direction::Up),
+            'D' => Ok(Direction::Down),
+            'L' => Ok(Direction::Left),
+            'R' => Ok(Direction::Right),
+            _ => Err("Unknown direction"),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Motion {
+    pub direction: Direction,
+    pub steps: u32,
+}
+
+pub fn read_motions<R>(reader: R) -> Result<Vec<Motion>, AocError>
+where
+    R: BufRead,
+{
+    reader
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Ok(l) if l.trim().is_empty()))
+        .map(|(line_idx, line)| {
+            let line = line?;
+            let parse_err =
+                |column, reason| AocError::parse(Day9::DAY, line_idx + 1, column, &line, reason);
+            let (direction, steps) = line
+                .split_once(' ')
+                .ok_or_else(|| parse_err(1, "Expected '<direction> <steps>'"))?;
+            let direction = match direction.chars().collect::<Vec<char>>()[..] {
+                [letter] => Direction::try_from(letter).map_err(|e| parse_err(1, e))?,
+                _ => return Err(parse_err(1, "Unknown direction")),
+            };
+            let steps = steps
+                .parse()
+                .map_err(|_| parse_err(line.len() - steps.len() + 1, "Invalid step count"))?;
+            Ok(Motion { direction, steps })
+        })
+        .collect()
+}
+
+fn follow(head: (i32, i32), tail: (i32, i32)) -> (i32, i32) {
+    let (dx, dy) = (head.0 - tail.0, head.1 - tail.1);
+    if dx.abs() <= 1 && dy.abs() <= 1 {
+        tail
+    } else {
+        (tail.0 + dx.signum(), tail.1 + dy.signum())
+    }
+}
+
+/// Number of distinct positions visited by the last knot of a rope with `knots` knots.
+pub fn tail_positions(motions: &[Motion], knots: usize) -> usize {
+    let mut rope = vec![(0, 0); knots.max(1)];
+    let mut visited = HashSet::from([(0, 0)]);
+    for motion in motions {
+        let (dx, dy) = motion.direction.delta();
+        for _ in 0..motion.steps {
+            rope[0] = (rope[0].0 + dx, rope[0].1 + dy);
+            for k in 1..rope.len() {
+                rope[k] = follow(rope[k - 1], rope[k]);
+            }
+            visited.insert(*rope.last().unwrap());
+        }
+    }
+    visited.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "R 4
+U 4
+L 3
+D 1
+R 4
+D 1
+L 5
+R 2
+";
+
+    const LARGER_EXAMPLE: &str = "R 5
+U 8
+L 8
+D 3
+R 17
+D 10
+L 25
+U 20
+";
+
+    #[test]
+    fn example_two_knots() {
+        let motions = read_motions(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(tail_positions(&motions, 2), 13);
+    }
+
+    #[test]
+    fn example_ten_knots() {
+        let motions = read_motions(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(tail_positions(&motions, 10), 1);
+        let motions = read_motions(LARGER_EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(tail_positions(&motions, 10), 36);
+    }
+
+    #[test]
+    fn invalid_motion_reports_position() {
+        match read_motions("R 4\nX 2\n".as_bytes()) {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (2, 1)),
+            other => panic!("unexpected result {:?}", other),
+        }
+        match read_motions("R four\n".as_bytes()) {
+            Err(AocError::Parse { column, .. }) => assert_eq!(column, 3),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+}
@@ -1,8 +1,190 @@
+use std::{
+    fmt::Display,
+    fs::File,
+    io::{BufRead, BufReader},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    answers::{AnswerStore, ANSWERS_FILE},
+    error::AocError,
+};
+
 pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
 pub mod day2;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
 pub mod day3;
 pub mod day4;
 pub mod day5;
 pub mod day6;
 pub mod day7;
-pub mod day8;
\ No newline at end of file
+pub mod day8;
+pub mod day9;
+
+/// A puzzle solution: the input is parsed once and shared by both parts.
+pub trait Solution {
+    const DAY: u8;
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Self::Input, AocError>;
+    fn part1(&self, input: &Self::Input) -> Result<Self::Answer1, AocError>;
+    fn part2(&self, input: &Self::Input) -> Result<Self::Answer2, AocError>;
+}
+
+/// One of the two parts of a puzzle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+/// Wall-clock time spent in each phase of a [`DynSolution::solve`] call; a part that was not
+/// run takes no time.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part1 + self.part2
+    }
+}
+
+/// The answers of the parts that were run.
+#[derive(Clone, Debug)]
+pub struct Answers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    pub timings: Timings,
+}
+
+impl Answers {
+    pub fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+}
+
+/// Type-erased view of a [`Solution`] so days can be kept in the [`REGISTRY`].
+pub trait DynSolution: Sync {
+    fn day(&self) -> u8;
+    /// Parses the input and runs `part`, or both parts for `None`. A part that is not run
+    /// cannot fail the other.
+    fn solve(&self, reader: &mut dyn BufRead, part: Option<Part>) -> Result<Answers, AocError>;
+}
+
+impl<S> DynSolution for S
+where
+    S: Solution + Sync,
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn solve(&self, reader: &mut dyn BufRead, part: Option<Part>) -> Result<Answers, AocError> {
+        let runs = |p: Part| part.is_none_or(|selected| selected == p);
+        let start = Instant::now();
+        let input = self.parse(reader)?;
+        let parsed = Instant::now();
+        let part1 = if runs(Part::One) {
+            Some(self.part1(&input)?.to_string())
+        } else {
+            None
+        };
+        let part1_done = Instant::now();
+        let part2 = if runs(Part::Two) {
+            Some(self.part2(&input)?.to_string())
+        } else {
+            None
+        };
+        let part2_done = Instant::now();
+        Ok(Answers {
+            part1,
+            part2,
+            timings: Timings {
+                parse: parsed - start,
+                part1: part1_done - parsed,
+                part2: part2_done - part1_done,
+            },
+        })
+    }
+}
+
+pub static REGISTRY: [&dyn DynSolution; 25] = [
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+    &day7::Day7,
+    &day8::Day8,
+    &day9::Day9,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+    &day13::Day13,
+    &day14::Day14,
+    &day15::Day15,
+    &day16::Day16,
+    &day17::Day17,
+    &day18::Day18,
+    &day19::Day19,
+    &day20::Day20,
+    &day21::Day21,
+    &day22::Day22,
+    &day23::Day23,
+    &day24::Day24,
+    &day25::Day25,
+];
+
+pub fn get(day: u8) -> Option<&'static dyn DynSolution> {
+    REGISTRY.iter().copied().find(|s| s.day() == day)
+}
+
+pub fn input_path(day: u8) -> String {
+    format!("data/input_day{}", day)
+}
+
+/// Solves `solution` against its committed input file and prints both parts, flagging any
+/// answer that differs from the one recorded in [`ANSWERS_FILE`].
+pub fn print_solution<S: Solution>(solution: &S) -> Result<(), AocError> {
+    let store = AnswerStore::load(ANSWERS_FILE)?;
+    let input = solution.parse(BufReader::new(File::open(input_path(S::DAY))?))?;
+    let part1 = solution.part1(&input)?.to_string();
+    println!("Part 1: {}", store.annotate(S::DAY, 1, &part1));
+    let part2 = solution.part2(&input)?.to_string();
+    println!("Part 2: {}", store.annotate(S::DAY, 2, &part2));
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    io,
+};
+
+#[derive(Debug)]
+pub enum AocError {
+    Io(io::Error),
+    /// Malformed puzzle input. `line` and `column` are 1-based, `text` is the offending line.
+    Parse {
+        day: u8,
+        line: usize,
+        column: usize,
+        text: String,
+        reason: String,
+    },
+    /// The input parsed fine but the puzzle has no answer for it.
+    NoSolution {
+        day: u8,
+        reason: String,
+    },
+}
+
+impl AocError {
+    pub fn parse<S: Into<String>>(
+        day: u8,
+        line: usize,
+        column: usize,
+        text: &str,
+        reason: S,
+    ) -> Self {
+        AocError::Parse {
+            day,
+            line,
+            column,
+            text: text.to_owned(),
+            reason: reason.into(),
+        }
+    }
+
+    pub fn no_solution<S: Into<String>>(day: u8, reason: S) -> Self {
+        AocError::NoSolution {
+            day,
+            reason: reason.into(),
+        }
+    }
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::Io(e) => write!(f, "I/O error: {}", e),
+            AocError::Parse {
+                day,
+                line,
+                column,
+                text,
+                reason,
+            } => write!(
+                f,
+                "day {}, line {}, column {}: {}: '{}'",
+                day, line, column, reason, text
+            ),
+            AocError::NoSolution { day, reason } => {
+                write!(f, "day {}: no solution: {}", day, reason)
+            }
+        }
+    }
+}
+
+impl Error for AocError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AocError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for AocError {
+    fn from(value: io::Error) -> Self {
+        AocError::Io(value)
+    }
+}
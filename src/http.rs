@@ -0,0 +1,235 @@
+//! Just enough HTTP/1.1 to talk to the puzzle site.
+//!
+//! Plain `http://` URLs are served over a `TcpStream`, which is what a local mock server speaks.
+//! The standard library has no TLS, so `https://` requests are handed to the system `curl`.
+
+use std::{
+    error::Error,
+    io::{Read, Write},
+    net::TcpStream,
+    process::{Command, Stdio},
+};
+
+const USER_AGENT: &str = concat!("aoc22/", env!("CARGO_PKG_VERSION"));
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Request<'a> {
+    pub url: &'a str,
+    pub cookie: &'a str,
+    /// `application/x-www-form-urlencoded` body; `None` sends a GET.
+    pub form: Option<String>,
+}
+
+pub fn send(request: &Request) -> Result<Response, Box<dyn Error>> {
+    // The cookie is pasted into a header line or a quoted curl option, so it must not end either.
+    if request
+        .cookie
+        .contains(|c: char| c.is_control() || c == '"' || c == '\\')
+    {
+        return Err("session cookie contains a quote, backslash or control character".into());
+    }
+    if let Some(rest) = request.url.strip_prefix("http://") {
+        send_plain(rest, request)
+    } else if request.url.starts_with("https://") {
+        send_curl(request)
+    } else {
+        Err(format!("unsupported URL '{}'", request.url).into())
+    }
+}
+
+/// Percent-encodes `value` for use in a form body.
+pub fn form_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn send_plain(rest: &str, request: &Request) -> Result<Response, Box<dyn Error>> {
+    let (host, path) = match rest.find('/') {
+        Some(idx) => rest.split_at(idx),
+        None => (rest, "/"),
+    };
+    let address = if host.contains(':') {
+        host.to_owned()
+    } else {
+        format!("{}:80", host)
+    };
+
+    let mut head = format!(
+        "{} {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: {}\r\nCookie: session={}\r\nConnection: close\r\n",
+        if request.form.is_some() { "POST" } else { "GET" },
+        path,
+        host,
+        USER_AGENT,
+        request.cookie
+    );
+    if let Some(form) = &request.form {
+        head += &format!(
+            "Content-Type: application/x-www-form-urlencoded\r\nContent-Length: {}\r\n",
+            form.len()
+        );
+    }
+    head += "\r\n";
+
+    let mut stream = TcpStream::connect(&address)
+        .map_err(|e| format!("cannot connect to {}: {}", address, e))?;
+    stream.write_all(head.as_bytes())?;
+    if let Some(form) = &request.form {
+        stream.write_all(form.as_bytes())?;
+    }
+    let mut raw = Vec::new();
+    stream.read_to_end(&mut raw)?;
+    parse_response(&raw)
+}
+
+fn parse_response(raw: &[u8]) -> Result<Response, Box<dyn Error>> {
+    let split = raw
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .ok_or("malformed HTTP response: no end of headers")?;
+    let head = String::from_utf8_lossy(&raw[..split]);
+    let body = &raw[split + 4..];
+
+    let mut lines = head.lines();
+    let status = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|code| code.parse().ok())
+        .ok_or("malformed HTTP response: bad status line")?;
+    let chunked = lines.any(|line| {
+        line.split_once(':').is_some_and(|(name, value)| {
+            name.eq_ignore_ascii_case("transfer-encoding") && value.trim() == "chunked"
+        })
+    });
+    let body = if chunked {
+        dechunk(body)?
+    } else {
+        body.to_vec()
+    };
+    Ok(Response {
+        status,
+        body: String::from_utf8(body)?,
+    })
+}
+
+fn dechunk(mut body: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut out = Vec::new();
+    loop {
+        let end = body
+            .windows(2)
+            .position(|w| w == b"\r\n")
+            .ok_or("malformed chunked body")?;
+        let size = std::str::from_utf8(&body[..end])?;
+        let size = usize::from_str_radix(size.split(';').next().unwrap_or("").trim(), 16)?;
+        if size == 0 {
+            return Ok(out);
+        }
+        let chunk = body
+            .get(end + 2..end + 2 + size)
+            .ok_or("truncated chunked body")?;
+        out.extend_from_slice(chunk);
+        body = body.get(end + 4 + size..).unwrap_or_default();
+    }
+}
+
+fn send_curl(request: &Request) -> Result<Response, Box<dyn Error>> {
+    // Options go through stdin so the session cookie never shows up in the process list.
+    let mut config = format!(
+        "header = \"Cookie: session={}\"\nuser-agent = \"{}\"\n",
+        request.cookie, USER_AGENT
+    );
+    if let Some(form) = &request.form {
+        config += &format!("data = \"{}\"\n", form);
+    }
+
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--config", "-"])
+        .args(["--write-out", "\n%{http_code}", request.url])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("cannot run curl for {}: {}", request.url, e))?;
+    child
+        .stdin
+        .take()
+        .ok_or("cannot write curl options")?
+        .write_all(config.as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(format!(
+            "curl failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )
+        .into());
+    }
+
+    let stdout = String::from_utf8(output.stdout)?;
+    let (body, status) = stdout
+        .rsplit_once('\n')
+        .ok_or("curl did not report a status code")?;
+    Ok(Response {
+        status: status.trim().parse()?,
+        body: body.to_owned(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_plain_response() {
+        let raw = b"HTTP/1.1 200 OK\r\nContent-Length: 4\r\n\r\n1\n2\n";
+        assert_eq!(
+            parse_response(raw).unwrap(),
+            Response {
+                status: 200,
+                body: "1\n2\n".to_owned()
+            }
+        );
+    }
+
+    #[test]
+    fn parse_chunked_response() {
+        let raw = b"HTTP/1.1 404 Not Found\r\nTransfer-Encoding: chunked\r\n\r\n3\r\nabc\r\n2\r\nde\r\n0\r\n\r\n";
+        assert_eq!(
+            parse_response(raw).unwrap(),
+            Response {
+                status: 404,
+                body: "abcde".to_owned()
+            }
+        );
+    }
+
+    #[test]
+    fn cookie_cannot_inject_headers_or_options() {
+        for cookie in ["abc\r\nX-Evil: 1", "abc\"\noutput = \"/tmp/x", "abc\\"] {
+            let request = Request {
+                url: "http://127.0.0.1:9/2022/day/1/input",
+                cookie,
+                form: None,
+            };
+            let err = send(&request).unwrap_err().to_string();
+            assert!(err.contains("session cookie"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn encode_form_values() {
+        assert_eq!(form_encode("2=-1=0"), "2%3D-1%3D0");
+        assert_eq!(form_encode("JSDHQMZGF"), "JSDHQMZGF");
+    }
+}
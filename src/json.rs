@@ -0,0 +1,88 @@
+//! One-line JSON records for `--format json`, so results can be ingested without scraping text.
+
+use std::{fmt::Write, time::Duration};
+
+/// `{"day":7,"part":1,"answer":1648397,"elapsed_ms":0.412}`; integer answers are JSON numbers,
+/// everything else a string.
+pub fn record(day: u8, part: u8, answer: &str, elapsed: Duration) -> String {
+    let answer = if is_integer(answer) {
+        answer.to_owned()
+    } else {
+        string(answer)
+    };
+    format!(
+        "{{\"day\":{},\"part\":{},\"answer\":{},\"elapsed_ms\":{:.3}}}",
+        day,
+        part,
+        answer,
+        elapsed.as_secs_f64() * 1000.0
+    )
+}
+
+/// Whether `value` is a JSON integer, `-?(0|[1-9][0-9]*)`, of any size.
+fn is_integer(value: &str) -> bool {
+    let digits = value.strip_prefix('-').unwrap_or(value).as_bytes();
+    match digits {
+        [b'0'] => true,
+        [b'1'..=b'9', rest @ ..] => rest.iter().all(u8::is_ascii_digit),
+        _ => false,
+    }
+}
+
+fn string(value: &str) -> String {
+    let mut out = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numeric_answer() {
+        assert_eq!(
+            record(7, 1, "1648397", Duration::from_micros(412)),
+            "{\"day\":7,\"part\":1,\"answer\":1648397,\"elapsed_ms\":0.412}"
+        );
+    }
+
+    #[test]
+    fn only_json_integers_are_bare() {
+        let answer = |text| record(1, 1, text, Duration::ZERO);
+        assert!(answer("-42").contains("\"answer\":-42,"));
+        assert!(answer("0").contains("\"answer\":0,"));
+        assert!(answer("123456789012345678901234567890")
+            .contains("\"answer\":123456789012345678901234567890,"));
+        for text in ["+5", "007", "-", "", "-0x1", "1e3"] {
+            assert!(
+                answer(text).contains(&format!("\"answer\":\"{}\"", text)),
+                "{}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn text_answers() {
+        assert_eq!(
+            record(5, 2, "JSDHQMZGF", Duration::ZERO),
+            "{\"day\":5,\"part\":2,\"answer\":\"JSDHQMZGF\",\"elapsed_ms\":0.000}"
+        );
+        assert_eq!(
+            record(10, 2, "\n#\"\\", Duration::ZERO),
+            "{\"day\":10,\"part\":2,\"answer\":\"\\n#\\\"\\\\\",\"elapsed_ms\":0.000}"
+        );
+    }
+}
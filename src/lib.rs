@@ -0,0 +1,28 @@
+//! Advent of Code 2022 solutions.
+//!
+//! Every day lives in [`days`] as a `dayN` module exposing its parser, solver functions and
+//! domain types, plus a unit struct implementing [`Solution`]. The [`days::REGISTRY`] maps day
+//! numbers to those implementations. Answers confirmed by the puzzle site are kept in an
+//! [`AnswerStore`] so later runs can flag regressions.
+
+pub mod answers;
+pub mod days;
+pub mod error;
+
+pub use answers::AnswerStore;
+pub use days::{DynSolution, Solution, REGISTRY};
+pub use error::AocError;
+
+pub use days::day1::{Elf, Expedition, Histogram, RankedElf};
+pub use days::day2::{Guide, GuideInterpretation, Outcome, Round, Rules, Score, Shape};
+pub use days::day3::{
+    GroupBadge, Grouping, ItemSet, LintFinding, LintIssue, Priority, Rucksack, SharedItem,
+};
+pub use days::day4::{
+    index::{ElfId, SectionIndex},
+    interval::Interval,
+    PairingError, ParseMode, RangePairing,
+};
+pub use days::day5::{replay::Simulator, CargoStacks, Crane, Move, MoveError};
+pub use days::day7::{DirElem, DirTree};
+pub use days::day8::Tree;
@@ -1,27 +1,271 @@
-pub mod days;
+use std::{
+    error::Error,
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+    process::ExitCode,
+    time::Duration,
+};
 
-fn main() {
-    println!("Day1:");
-    days::day1::print_answer();
+use aoc22::{
+    answers::ANSWERS_FILE,
+    days::{
+        self, day3,
+        day5::{Crane, Day5},
+        Part, Solution, Timings,
+    },
+    AnswerStore, DynSolution,
+};
+use cli::{Command, Days, Format, Input, RunArgs};
+use config::Config;
+use remote::Verdict;
 
-    println!("Day2:");
-    days::day2::print_answer();
+mod cli;
+mod config;
+mod http;
+mod json;
+mod remote;
 
-    println!("Day3:");
-    days::day3::print_answer();
+fn open_input(day: u8, input: &Input) -> Result<Box<dyn BufRead>, Box<dyn Error>> {
+    let path = match input {
+        Input::Stdin => return Ok(Box::new(io::stdin().lock())),
+        Input::Default => days::input_path(day),
+        Input::File(path) => path.to_owned(),
+    };
+    match File::open(&path) {
+        Ok(file) => Ok(Box::new(BufReader::new(file))),
+        Err(e) => Err(format!("cannot open {}: {}", path, e).into()),
+    }
+}
 
-    println!("Day4:");
-    days::day4::print_answer();
+/// Runs one day, returning its timings and how many printed answers differ from `store`.
+fn run_day(
+    solution: &dyn DynSolution,
+    args: &RunArgs,
+    store: &AnswerStore,
+) -> Result<(Timings, usize), Box<dyn Error>> {
+    let day = solution.day();
+    let mut reader = open_input(day, &args.input)?;
+    let answers = solution.solve(&mut reader, args.part)?;
+    if args.format == Format::Text {
+        println!("Day{}:", day);
+    }
+    let mut regressions = 0;
+    for (part, elapsed) in [
+        (Part::One, answers.timings.part1),
+        (Part::Two, answers.timings.part2),
+    ] {
+        let Some(answer) = answers.get(part) else {
+            continue;
+        };
+        let n = part.number();
+        regressions += usize::from(store.regression(day, n, answer).is_some());
+        match args.format {
+            // Multi-line answers such as day 10's image start on their own line.
+            Format::Text if answer.contains('\n') => {
+                println!("Part {}:\n{}", n, store.annotate(day, n, answer))
+            }
+            Format::Text => println!("Part {}: {}", n, store.annotate(day, n, answer)),
+            Format::Json => println!("{}", json::record(day, n, answer, elapsed)),
+        }
+    }
+    Ok((answers.timings, regressions))
+}
 
-    println!("Day5:");
-    days::day5::print_answer();
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+fn print_timings(timings: &[(u8, Timings)]) {
+    println!();
+    println!(
+        "{:>3} {:>12} {:>12} {:>12} {:>12}",
+        "Day", "Parse (ms)", "Part 1 (ms)", "Part 2 (ms)", "Total (ms)"
+    );
+    for (day, t) in timings {
+        println!(
+            "{:>3} {:>12.3} {:>12.3} {:>12.3} {:>12.3}",
+            day,
+            millis(t.parse),
+            millis(t.part1),
+            millis(t.part2),
+            millis(t.total())
+        );
+    }
+    if timings.len() > 1 {
+        let total = timings.iter().map(|(_, t)| t.total()).sum();
+        println!("{:>3} {:>51.3}", "All", millis(total));
+    }
+}
 
-    println!("Day6:");
-    days::day6::print_answer();
+/// Replays the day 5 procedure, printing the drawing before the first and after every move.
+fn trace(args: &RunArgs) -> Result<(), Box<dyn Error>> {
+    let day = match args.days {
+        Days::Single(day) => day,
+        Days::All => unreachable!("the command line requires a single day for --trace"),
+    };
+    if day != Day5::DAY {
+        return Err(format!("day {} has no trace, only day {} does", day, Day5::DAY).into());
+    }
+    let (cargo, moves) = Day5.parse(open_input(day, &args.input)?)?;
+    for (part, crane) in [
+        (Part::One, Crane::CrateMover9000),
+        (Part::Two, Crane::CrateMover9001),
+    ] {
+        if args.part.is_some_and(|p| p != part) {
+            continue;
+        }
+        println!("Part {} with the {}:", part.number(), crane);
+        println!("{}", cargo);
+        let mut cargo = cargo.clone();
+        cargo.move_cargo_traced(&moves, crane, |mv, cargo| {
+            println!("\n{}\n{}", mv, cargo);
+        })?;
+        println!("\nPart {}: {}\n", part.number(), cargo.top_crates_str());
+    }
+    Ok(())
+}
+
+fn run(args: &RunArgs) -> Result<(), Box<dyn Error>> {
+    if args.trace {
+        return trace(args);
+    }
+    let solutions = match args.days {
+        Days::All => days::REGISTRY.to_vec(),
+        Days::Single(day) => match days::get(day) {
+            Some(solution) => vec![solution],
+            None => return Err(format!("day {} is not implemented", day).into()),
+        },
+    };
+
+    // Recorded answers belong to the default inputs, so other inputs are not compared.
+    let store = match args.input {
+        Input::Default => AnswerStore::load(ANSWERS_FILE)?,
+        _ => AnswerStore::default(),
+    };
+    let mut regressions = 0;
+    let timings = solutions
+        .into_iter()
+        .filter(|solution| {
+            // Running everything should not fail just because some inputs were never downloaded.
+            let missing = args.days == Days::All
+                && args.input == Input::Default
+                && !Path::new(&days::input_path(solution.day())).exists();
+            if missing {
+                eprintln!(
+                    "Day{}: skipped, no {}",
+                    solution.day(),
+                    days::input_path(solution.day())
+                );
+            }
+            !missing
+        })
+        .map(|solution| {
+            let (timings, changed) = run_day(solution, args, &store)?;
+            regressions += changed;
+            Ok((solution.day(), timings))
+        })
+        .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+    if args.time {
+        print_timings(&timings);
+    }
+    if regressions > 0 {
+        return Err(format!("{} answer(s) differ from {}", regressions, ANSWERS_FILE).into());
+    }
+    Ok(())
+}
+
+fn fetch(day: u8) -> Result<(), Box<dyn Error>> {
+    if days::get(day).is_none() {
+        return Err(format!("day {} is not implemented", day).into());
+    }
+    let path = days::input_path(day);
+    remote::fetch_input(day, &Config::load()?, Path::new(&path))?;
+    println!("Saved day {} input to {}", day, path);
+    Ok(())
+}
+
+fn submit(day: u8, part: Part) -> Result<(), Box<dyn Error>> {
+    let solution = days::get(day).ok_or_else(|| format!("day {} is not implemented", day))?;
+    let answers = solution.solve(&mut open_input(day, &Input::Default)?, Some(part))?;
+    let n = part.number();
+    let answer = answers
+        .get(part)
+        .expect("the selected part was run")
+        .to_owned();
+    if answer.contains(char::is_whitespace) {
+        return Err(format!(
+            "day {} part {} answer spans several lines, read and submit it by hand:\n{}",
+            day, n, answer
+        )
+        .into());
+    }
+
+    let mut store = AnswerStore::load(ANSWERS_FILE)?;
+    match store.get(day, n) {
+        Some(recorded) if recorded == answer => {
+            println!("Day {} part {}: {} is already confirmed", day, n, answer);
+            return Ok(());
+        }
+        Some(recorded) => {
+            return Err(format!(
+                "day {} part {} was already confirmed as {}, not submitting {}",
+                day, n, recorded, answer
+            )
+            .into())
+        }
+        None => {}
+    }
+
+    let verdict = remote::submit_answer(day, n, &answer, &Config::load()?)?;
+    if verdict != Verdict::Correct {
+        return Err(format!("day {} part {}: {} is {}", day, n, answer, verdict).into());
+    }
+    store.record(day, n, &answer);
+    store.save(ANSWERS_FILE)?;
+    println!(
+        "Day {} part {}: {} is correct, recorded in {}",
+        day, n, answer, ANSWERS_FILE
+    );
+    Ok(())
+}
+
+fn lint(day: u8, input: &Input) -> Result<(), Box<dyn Error>> {
+    let findings = match day {
+        3 => day3::lint_rucksacks(open_input(day, input)?)?,
+        _ => return Err(format!("day {} has no linter", day).into()),
+    };
+    for finding in &findings {
+        println!("{}", finding);
+    }
+    if !findings.is_empty() {
+        return Err(format!("{} problem(s) in day {} input", findings.len(), day).into());
+    }
+    println!("Day {} input is well formed", day);
+    Ok(())
+}
 
-    println!("Day7:");
-    days::day7::print_answer();
+fn main() -> ExitCode {
+    let result = match cli::parse_args(std::env::args().skip(1)) {
+        Ok(Command::Run(args)) => run(&args),
+        Ok(Command::Fetch(day)) => fetch(day),
+        Ok(Command::Submit(day, part)) => submit(day, part),
+        Ok(Command::Lint(day, input)) => lint(day, &input),
+        Ok(Command::Help) => {
+            println!("{}", cli::USAGE);
+            Ok(())
+        }
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, cli::USAGE);
+            return ExitCode::from(2);
+        }
+    };
 
-    println!("Day8:");
-    days::day8::print_answer();
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
 }
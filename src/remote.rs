@@ -0,0 +1,265 @@
+//! Downloading puzzle inputs from, and submitting answers to, the puzzle site configured in
+//! [`Config`].
+
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    fs,
+    path::Path,
+};
+
+use crate::{
+    config::Config,
+    http::{self, Request},
+};
+
+/// Downloads the input for `day` into `path`, refusing to overwrite an input already cached there.
+pub fn fetch_input(day: u8, config: &Config, path: &Path) -> Result<(), Box<dyn Error>> {
+    if path.exists() {
+        return Err(format!(
+            "{} already exists, delete it to download again",
+            path.display()
+        )
+        .into());
+    }
+
+    let url = format!("{}/day/{}/input", config.base_url, day);
+    let response = http::send(&Request {
+        url: &url,
+        cookie: config.session()?,
+        form: None,
+    })?;
+    if response.status != 200 {
+        return Err(format!(
+            "cannot fetch {}: HTTP {}: {}",
+            url,
+            response.status,
+            response.body.lines().next().unwrap_or("").trim()
+        )
+        .into());
+    }
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, response.body)
+        .map_err(|e| format!("cannot write {}: {}", path.display(), e).into())
+}
+
+/// The site's reply to a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// Submitted too soon after a wrong answer; holds the remaining wait, e.g. `"35s"`.
+    TooSoon(String),
+    /// The part is already solved or not yet unlocked.
+    WrongLevel,
+}
+
+impl Verdict {
+    /// Recognises the verdict in the HTML page returned after posting an answer.
+    pub fn from_page(page: &str) -> Option<Verdict> {
+        if page.contains("That's the right answer") {
+            Some(Verdict::Correct)
+        } else if page.contains("That's not the right answer") {
+            Some(if page.contains("your answer is too high") {
+                Verdict::TooHigh
+            } else if page.contains("your answer is too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Incorrect
+            })
+        } else if page.contains("You gave an answer too recently") {
+            let wait = page
+                .split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .map_or("some time", |(wait, _)| wait);
+            Some(Verdict::TooSoon(wait.to_owned()))
+        } else if page.contains("You don't seem to be solving the right level") {
+            Some(Verdict::WrongLevel)
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Incorrect => write!(f, "incorrect"),
+            Verdict::TooHigh => write!(f, "incorrect, too high"),
+            Verdict::TooLow => write!(f, "incorrect, too low"),
+            Verdict::TooSoon(wait) => write!(f, "answered too recently, wait {}", wait),
+            Verdict::WrongLevel => write!(f, "part already solved or still locked"),
+        }
+    }
+}
+
+/// Posts `answer` for `day` and `part` and reports what the site made of it.
+pub fn submit_answer(
+    day: u8,
+    part: u8,
+    answer: &str,
+    config: &Config,
+) -> Result<Verdict, Box<dyn Error>> {
+    let url = format!("{}/day/{}/answer", config.base_url, day);
+    let response = http::send(&Request {
+        url: &url,
+        cookie: config.session()?,
+        form: Some(format!(
+            "level={}&answer={}",
+            part,
+            http::form_encode(answer)
+        )),
+    })?;
+    if response.status != 200 {
+        return Err(format!("cannot submit to {}: HTTP {}", url, response.status).into());
+    }
+    Verdict::from_page(&response.body)
+        .ok_or_else(|| format!("unrecognised response from {}", url).into())
+}
+
+#[cfg(test)]
+pub(crate) mod mock {
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        path::PathBuf,
+        thread::{self, JoinHandle},
+    };
+
+    /// Serves one canned response on a local port and hands back the raw request it received.
+    pub fn serve(status: &str, body: &'static str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/2022", listener.local_addr().unwrap());
+        let status = status.to_owned();
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0; 1024];
+            while !request_complete(&request) {
+                let n = stream.read(&mut buf).unwrap();
+                if n == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..n]);
+            }
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+            String::from_utf8(request).unwrap()
+        });
+        (base_url, handle)
+    }
+
+    fn request_complete(request: &[u8]) -> bool {
+        let Some(end) = request.windows(4).position(|w| w == b"\r\n\r\n") else {
+            return false;
+        };
+        let head = String::from_utf8_lossy(&request[..end]);
+        let length = head
+            .lines()
+            .filter_map(|line| line.strip_prefix("Content-Length: "))
+            .find_map(|n| n.parse::<usize>().ok())
+            .unwrap_or(0);
+        request.len() >= end + 4 + length
+    }
+
+    /// A fresh, empty scratch directory for one test.
+    pub fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc22-{}-{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{mock::*, *};
+
+    fn config(base_url: String) -> Config {
+        Config {
+            base_url,
+            session: Some("cookie".to_owned()),
+        }
+    }
+
+    #[test]
+    fn fetch_caches_input() {
+        let (base_url, server) = serve("200 OK", "1000\n2000\n");
+        let path = scratch_dir("fetch").join("data/input_day1");
+        fetch_input(1, &config(base_url), &path).unwrap();
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2022/day/1/input HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=cookie\r\n"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1000\n2000\n");
+
+        // Nothing is listening any more, so this only passes if no request is made.
+        let err = fetch_input(1, &config("http://127.0.0.1:9".to_owned()), &path).unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+    }
+
+    #[test]
+    fn failed_fetch_writes_nothing() {
+        let (base_url, server) = serve("400 Bad Request", "Puzzle inputs differ by user.\n");
+        let path = scratch_dir("fetch-fail").join("input_day3");
+        let err = fetch_input(3, &config(base_url), &path).unwrap_err();
+        server.join().unwrap();
+        assert!(err
+            .to_string()
+            .contains("HTTP 400: Puzzle inputs differ by user."));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn fetch_needs_session() {
+        let config = Config {
+            base_url: "http://127.0.0.1:9".to_owned(),
+            session: None,
+        };
+        let path = scratch_dir("fetch-session").join("input_day2");
+        assert!(fetch_input(2, &config, &path).is_err());
+    }
+
+    #[test]
+    fn submit_posts_form() {
+        let page =
+            "<article><p>That's the right answer!  You are one gold star closer.</p></article>";
+        let (base_url, server) = serve("200 OK", page);
+        let verdict = submit_answer(25, 1, "2=-1=0", &config(base_url)).unwrap();
+        assert_eq!(verdict, Verdict::Correct);
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2022/day/25/answer HTTP/1.1\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=1&answer=2%3D-1%3D0"));
+    }
+
+    #[test]
+    fn verdicts() {
+        let wrong = "That's not the right answer; your answer is too high.  If you're stuck";
+        assert_eq!(Verdict::from_page(wrong), Some(Verdict::TooHigh));
+        let wrong = "That's not the right answer; your answer is too low.";
+        assert_eq!(Verdict::from_page(wrong), Some(Verdict::TooLow));
+        let wrong = "That's not the right answer.  If you're stuck, make sure";
+        assert_eq!(Verdict::from_page(wrong), Some(Verdict::Incorrect));
+        let soon = "You gave an answer too recently; you have to wait after submitting an answer \
+                    before trying again.  You have 35s left to wait.";
+        assert_eq!(
+            Verdict::from_page(soon),
+            Some(Verdict::TooSoon("35s".to_owned()))
+        );
+        let level = "You don't seem to be solving the right level.  Did you already complete it?";
+        assert_eq!(Verdict::from_page(level), Some(Verdict::WrongLevel));
+        assert_eq!(Verdict::from_page("<html></html>"), None);
+    }
+}
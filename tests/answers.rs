@@ -0,0 +1,47 @@
+use std::{
+    fs::{self, File},
+    io::BufReader,
+};
+
+use aoc22::days;
+
+/// Answers for the committed `data/input_dayN` files.
+const ANSWERS: [(u8, &str, &str); 8] = [
+    (1, "74394", "212836"),
+    (2, "14827", "13889"),
+    (3, "8039", "2510"),
+    (4, "431", "823"),
+    (5, "FZCMJCRHZ", "JSDHQMZGF"),
+    (6, "1480", "2746"),
+    (7, "1648397", "1815525"),
+    (8, "1843", "180000"),
+];
+
+#[test]
+fn committed_inputs() {
+    for (day, expected1, expected2) in ANSWERS {
+        let solution = days::get(day).unwrap();
+        let mut reader = BufReader::new(File::open(days::input_path(day)).unwrap());
+        let answers = solution.solve(&mut reader, None).unwrap();
+        assert_eq!(answers.part1.unwrap(), expected1, "day {} part 1", day);
+        assert_eq!(answers.part2.unwrap(), expected2, "day {} part 2", day);
+    }
+}
+
+#[test]
+fn registry_covers_calendar() {
+    let registered = days::REGISTRY.iter().map(|s| s.day()).collect::<Vec<u8>>();
+    assert_eq!(registered, (1..=25).collect::<Vec<u8>>());
+}
+
+/// Fetched inputs are git-ignored unless they are committed with their answers.
+#[test]
+fn every_committed_input_has_answers() {
+    let gitignore = fs::read_to_string(".gitignore").unwrap();
+    let committed = gitignore
+        .lines()
+        .filter_map(|line| line.strip_prefix("!/data/input_day"))
+        .map(|day| day.parse().unwrap())
+        .collect::<Vec<u8>>();
+    assert_eq!(committed, ANSWERS.map(|(day, _, _)| day));
+}
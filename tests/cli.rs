@@ -0,0 +1,39 @@
+use std::{
+    io::Write,
+    process::{Command, Output, Stdio},
+};
+
+/// Runs the `aoc22` binary with `args`, feeding `input` on stdin.
+fn aoc22(args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_aoc22"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn selected_part_runs_when_the_other_fails() {
+    // Four distinct characters make a start-of-packet marker, but there is no message marker.
+    let output = aoc22(&["run", "-d", "6", "-p", "1", "-i", "-"], "abcdxx\n");
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Day6:\nPart 1: 4\n"
+    );
+
+    let output = aoc22(&["run", "-d", "6", "-i", "-"], "abcdxx\n");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("no start-of-message marker"));
+}